@@ -0,0 +1,250 @@
+use crate::sprite::{Sprite, SpriteSheet};
+
+/// How an [`Animation`] continues after it's last frame.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AnimationMode {
+    /// Start over at the first frame.
+    Loop,
+    /// Play the frames backwards back to the first frame, then forwards again, and so on.
+    PingPong,
+    /// Stay at the last frame.
+    Once,
+}
+
+/// A sequence of frames, each shown for it's own duration,
+/// that can be drawn with [`Window::animation`], which picks the current frame
+/// and advances the animation by [`Window::deltatime`].
+///
+/// # Examples
+/// ```
+/// let mut pg = Window::new(800, 600, "Window Title");
+/// let sheet = SpriteSheet::new(pg.load_texture("player.png").unwrap(), 8, 4);
+/// // The first row of the sheet at 10 frames per second
+/// let mut walk = Animation::from_sprite_sheet(sheet, 0..8, 0.1, AnimationMode::Loop);
+/// loop {
+///     pg.animation((100., 100.), &mut walk, 64., 64.);
+///     pg.update();
+/// }
+/// ```
+///
+/// [`Window::animation`]: crate::window::Window::animation
+/// [`Window::deltatime`]: crate::window::Window::deltatime
+#[derive(Clone, PartialEq)]
+pub struct Animation {
+    frames: Vec<(Sprite, f32)>,
+    mode: AnimationMode,
+    time: f32,
+    /// The frame number of the window when the animation was last advanced by [`Window::animation`],
+    /// so that it is advanced at most once per frame.
+    ///
+    /// [`Window::animation`]: crate::window::Window::animation
+    advanced_at: Option<u64>,
+}
+
+impl Animation {
+    /// Create an animation without any frames, to be added with [`Animation::frame`].
+    pub fn new(mode: AnimationMode) -> Self {
+        Self {
+            frames: Vec::new(),
+            mode,
+            time: 0.,
+            advanced_at: None,
+        }
+    }
+
+    /// Create an animation from the frames of `sheet` with numbers `frames`
+    /// (See [`SpriteSheet::frame`]), each shown for `frame_duration` seconds.
+    pub fn from_sprite_sheet<I: IntoIterator<Item = usize>>(
+        sheet: SpriteSheet,
+        frames: I,
+        frame_duration: f32,
+        mode: AnimationMode,
+    ) -> Self {
+        frames.into_iter().fold(Self::new(mode), |animation, i| {
+            animation.frame(sheet.frame(i), frame_duration)
+        })
+    }
+
+    /// Append the frame `sprite`, shown for `duration` seconds.
+    pub fn frame(mut self, sprite: Sprite, duration: f32) -> Self {
+        self.frames.push((sprite, duration.max(0.)));
+        self
+    }
+
+    /// The order in which the frames are shown during one run of the animation.
+    fn sequence(&self) -> impl Iterator<Item = usize> {
+        let n = self.frames.len();
+        let back = match self.mode {
+            AnimationMode::PingPong => 1..n.saturating_sub(1),
+            _ => 0..0,
+        };
+        (0..n).chain(back.rev())
+    }
+
+    /// How long one run of the animation takes in seconds.
+    /// For [`AnimationMode::PingPong`], this includes playing the frames backwards.
+    pub fn duration(&self) -> f32 {
+        self.sequence().map(|i| self.frames[i].1).sum()
+    }
+
+    /// Advance the animation by `seconds`.
+    /// This is done automatically when drawing the animation with [`Window::animation`].
+    ///
+    /// [`Window::animation`]: crate::window::Window::animation
+    pub fn advance(&mut self, seconds: f32) {
+        self.time += seconds;
+        let duration = self.duration();
+        match self.mode {
+            AnimationMode::Loop | AnimationMode::PingPong if duration > 0. => {
+                self.time %= duration;
+            }
+            _ => {}
+        }
+    }
+
+    /// Advance the animation by `seconds`, unless it was already advanced in frame number `frame`.
+    pub(crate) fn advance_once(&mut self, frame: u64, seconds: f32) {
+        if self.advanced_at != Some(frame) {
+            self.advanced_at = Some(frame);
+            self.advance(seconds);
+        }
+    }
+
+    /// Go back to the first frame.
+    pub fn restart(&mut self) {
+        self.time = 0.;
+    }
+
+    /// Whether an animation in [`AnimationMode::Once`] has reached the end of it's last frame.
+    /// Animations in other modes never finish.
+    pub fn is_finished(&self) -> bool {
+        self.mode == AnimationMode::Once && self.time >= self.duration()
+    }
+
+    /// The number of the frame currently shown, in the order the frames were added.
+    pub fn current_index(&self) -> Option<usize> {
+        let mut t = self.time;
+        let mut last = None;
+        for i in self.sequence() {
+            last = Some(i);
+            t -= self.frames[i].1;
+            if t < 0. {
+                break;
+            }
+        }
+        last
+    }
+
+    /// The frame currently shown, or `None` if the animation has no frames.
+    pub fn current(&self) -> Option<Sprite> {
+        self.current_index().map(|i| self.frames[i].0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::texture::Texture;
+
+    /// An animation with `n` frames of one second each,
+    /// where frame `i` shows the `i`-th 16 pixel wide part of a texture.
+    fn animation(n: i32, mode: AnimationMode) -> Animation {
+        let texture = Texture {
+            index: 0,
+            generation: 0,
+        };
+        (0..n).fold(Animation::new(mode), |animation, i| {
+            let sprite = Sprite {
+                texture,
+                region: (i * 16, 0, 16, 16),
+            };
+            animation.frame(sprite, 1.)
+        })
+    }
+
+    /// The frame numbers shown after advancing `animation` by `step` seconds `steps` times.
+    fn indices(
+        animation: &mut Animation,
+        step: f32,
+        steps: usize,
+    ) -> Vec<usize> {
+        (0..steps)
+            .map(|_| {
+                animation.advance(step);
+                animation.current_index().unwrap()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn loop_starts_over() {
+        let mut walk = animation(3, AnimationMode::Loop);
+        assert_eq!(walk.current_index(), Some(0));
+        assert_eq!(walk.duration(), 3.);
+        assert_eq!(indices(&mut walk, 0.5, 8), [0, 1, 1, 2, 2, 0, 0, 1]);
+        assert!(!walk.is_finished());
+    }
+
+    #[test]
+    fn ping_pong_turns_at_both_ends() {
+        let mut bounce = animation(3, AnimationMode::PingPong);
+        // Forwards to the last frame, backwards without repeating it or the first frame
+        assert_eq!(bounce.duration(), 4.);
+        assert_eq!(indices(&mut bounce, 1., 9), [1, 2, 1, 0, 1, 2, 1, 0, 1]);
+
+        let mut two = animation(2, AnimationMode::PingPong);
+        assert_eq!(indices(&mut two, 1., 4), [1, 0, 1, 0]);
+    }
+
+    #[test]
+    fn once_stays_at_last_frame() {
+        let mut explosion = animation(3, AnimationMode::Once);
+        assert_eq!(indices(&mut explosion, 1., 2), [1, 2]);
+        assert!(!explosion.is_finished());
+        explosion.advance(0.5);
+        assert!(!explosion.is_finished());
+        explosion.advance(10.);
+        assert!(explosion.is_finished());
+        assert_eq!(explosion.current_index(), Some(2));
+        assert!(explosion.current().unwrap().region == (32, 0, 16, 16));
+
+        explosion.restart();
+        assert!(!explosion.is_finished());
+        assert_eq!(explosion.current_index(), Some(0));
+    }
+
+    #[test]
+    fn single_frame() {
+        for mode in [
+            AnimationMode::Loop,
+            AnimationMode::PingPong,
+            AnimationMode::Once,
+        ] {
+            let mut still = animation(1, mode);
+            assert_eq!(still.duration(), 1.);
+            assert_eq!(indices(&mut still, 0.7, 5), [0; 5]);
+        }
+    }
+
+    #[test]
+    fn no_frames() {
+        let mut empty = animation(0, AnimationMode::Loop);
+        empty.advance(1.);
+        assert_eq!(empty.current_index(), None);
+        assert!(empty.current().is_none());
+    }
+
+    #[test]
+    fn advance_once_per_frame() {
+        let mut walk = animation(3, AnimationMode::Loop);
+        // Drawn twice in frame 0, but only advanced once
+        walk.advance_once(0, 1.);
+        walk.advance_once(0, 1.);
+        assert_eq!(walk.current_index(), Some(1));
+        walk.advance_once(1, 1.);
+        walk.advance_once(1, 1.);
+        assert_eq!(walk.current_index(), Some(2));
+        walk.advance_once(2, 1.);
+        assert_eq!(walk.current_index(), Some(0));
+    }
+}
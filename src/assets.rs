@@ -0,0 +1,197 @@
+//! The textures and fonts loaded by the program.
+//!
+//! All windows of a thread share one [`Assets`] context through an [`Rc`],
+//! which is also reachable from the [`Texture`] and [`Font`] handles (See [`with_assets`]),
+//! so that functions like [`Texture::width`] work without a reference to a window.
+//! The assets are freed once the last window of the thread is dropped.
+//! Handles to them stay invalid afterwards, even if new windows load new assets.
+//!
+//! Since SFML shapes and texts borrow the textures and fonts they are drawn with,
+//! they are created for each draw while the assets are borrowed, instead of being kept around.
+
+use std::{
+    cell::RefCell,
+    rc::{Rc, Weak},
+};
+
+use sfml::{
+    graphics::{Font as SfmlFont, RenderTexture, Texture as SfmlTexture},
+    SfBox,
+};
+
+use crate::{
+    canvas::Canvas,
+    error::Error,
+    font::{load_default_font, Font, StoredFont},
+    store::Store,
+    texture::{StoredTexture, Texture},
+};
+
+pub struct Assets {
+    textures: Store<StoredTexture>,
+    fonts: Store<StoredFont>,
+    default_font: Result<StoredFont, Error>,
+}
+
+impl Assets {
+    fn new() -> Self {
+        Self {
+            textures: Store::new(),
+            fonts: Store::new(),
+            default_font: load_default_font(),
+        }
+    }
+
+    /// The SFML texture for `texture`, or `None` if it has been unloaded.
+    pub fn texture(&self, texture: Texture) -> Option<&SfmlTexture> {
+        self.textures
+            .get(texture.index, texture.generation)?
+            .sfml_texture()
+    }
+
+    /// The SFML texture for `texture`, unless it is the texture of a canvas.
+    pub fn texture_mut(
+        &mut self,
+        texture: Texture,
+    ) -> Option<&mut SfmlTexture> {
+        match self.stored_texture_mut(texture)? {
+            StoredTexture::Texture(t) => Some(t),
+            StoredTexture::Canvas(_) => None,
+        }
+    }
+
+    pub fn stored_texture_mut(
+        &mut self,
+        texture: Texture,
+    ) -> Option<&mut StoredTexture> {
+        self.textures.get_mut(texture.index, texture.generation)
+    }
+
+    pub fn add_texture(&mut self, texture: SfBox<SfmlTexture>) -> Texture {
+        let (index, generation) =
+            self.textures.add(StoredTexture::Texture(texture));
+        Texture { index, generation }
+    }
+
+    /// Replace the SFML texture for `texture` with `new`, keeping it's settings.
+    /// Does nothing if `texture` has been unloaded or is the texture of a canvas.
+    pub fn replace_texture(
+        &mut self,
+        texture: Texture,
+        mut new: SfBox<SfmlTexture>,
+    ) {
+        if let Some(StoredTexture::Texture(old)) =
+            self.stored_texture_mut(texture)
+        {
+            new.set_smooth(old.is_smooth());
+            new.set_repeated(old.is_repeated());
+            *old = new;
+        }
+    }
+
+    pub fn add_canvas(&mut self, render_texture: RenderTexture) -> Canvas {
+        let (index, generation) = self
+            .textures
+            .add(StoredTexture::Canvas(Some(render_texture)));
+        Canvas::new(Texture { index, generation })
+    }
+
+    /// Take the render texture of `canvas` out of the store to draw to it.
+    /// It has to be put back with [`Assets::return_canvas`].
+    pub fn take_canvas(&mut self, canvas: Canvas) -> Option<RenderTexture> {
+        match self.stored_texture_mut(canvas.texture())? {
+            StoredTexture::Canvas(rt) => rt.take(),
+            StoredTexture::Texture(_) => None,
+        }
+    }
+
+    pub fn return_canvas(
+        &mut self,
+        canvas: Canvas,
+        render_texture: RenderTexture,
+    ) {
+        if let Some(StoredTexture::Canvas(rt)) =
+            self.stored_texture_mut(canvas.texture())
+        {
+            *rt = Some(render_texture);
+        }
+    }
+
+    /// Remove `texture`, returning whether it was still there.
+    pub fn remove_texture(&mut self, texture: Texture) -> bool {
+        self.textures
+            .remove(texture.index, texture.generation)
+            .is_some()
+    }
+
+    /// The SFML font for `font`, or `None` if it has been unloaded.
+    pub fn font(&self, font: Font) -> Option<&SfmlFont> {
+        self.fonts
+            .get(font.index, font.generation)
+            .map(|f| &*f.font)
+    }
+
+    /// The SFML font for `font`, or the default font if `font` is `None` or has been unloaded.
+    pub fn font_or_default(&self, font: Option<Font>) -> Option<&SfmlFont> {
+        font.and_then(|f| self.font(f))
+            .or_else(|| self.default_font.as_ref().ok().map(|f| &*f.font))
+    }
+
+    /// Why the default font could not be loaded, if it could not.
+    pub fn default_font_error(&self) -> Option<Error> {
+        self.default_font.as_ref().err().cloned()
+    }
+
+    pub fn add_font(&mut self, font: StoredFont) -> Font {
+        let (index, generation) = self.fonts.add(font);
+        Font { index, generation }
+    }
+
+    /// Replace the stored font for `font` with `new`.
+    /// Does nothing if `font` has been unloaded.
+    pub fn replace_font(&mut self, font: Font, new: StoredFont) {
+        if let Some(old) = self.fonts.get_mut(font.index, font.generation) {
+            *old = new;
+        }
+    }
+
+    /// Remove `font`, returning whether it was still there.
+    pub fn remove_font(&mut self, font: Font) -> bool {
+        self.fonts.remove(font.index, font.generation).is_some()
+    }
+}
+
+thread_local! {
+    static SHARED_ASSETS: RefCell<Weak<RefCell<Assets>>> =
+        const { RefCell::new(Weak::new()) };
+}
+
+/// The assets shared by all windows of this thread,
+/// created if there are no windows yet.
+pub fn shared_assets() -> Rc<RefCell<Assets>> {
+    SHARED_ASSETS.with(|shared| {
+        let mut shared = shared.borrow_mut();
+        shared.upgrade().unwrap_or_else(|| {
+            let assets = Rc::new(RefCell::new(Assets::new()));
+            *shared = Rc::downgrade(&assets);
+            assets
+        })
+    })
+}
+
+/// Run `f` with the assets shared by the windows of this thread.
+/// Returns `None` if there are no windows.
+pub fn with_assets<R>(f: impl FnOnce(&Assets) -> Option<R>) -> Option<R> {
+    let assets = SHARED_ASSETS.with(|shared| shared.borrow().upgrade())?;
+    let assets = assets.borrow();
+    f(&assets)
+}
+
+/// Like [`with_assets`], but with mutable access to the assets.
+pub fn with_assets_mut<R>(
+    f: impl FnOnce(&mut Assets) -> Option<R>,
+) -> Option<R> {
+    let assets = SHARED_ASSETS.with(|shared| shared.borrow().upgrade())?;
+    let mut assets = assets.borrow_mut();
+    f(&mut assets)
+}
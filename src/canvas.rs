@@ -0,0 +1,58 @@
+use crate::texture::Texture;
+
+/// An offscreen image that can be drawn to like the window, created with [`Window::create_canvas`].
+/// While a canvas is set as the target with [`Window::canvas`], all drawing goes to the canvas
+/// instead of the window.
+/// What is drawn to a canvas stays there across frames, until it is cleared with [`Window::clear`].
+/// The content of a canvas can be drawn like any other texture through [`Canvas::texture`].
+///
+/// # Examples
+/// ```
+/// let mut pg = Window::new(800, 600, "Window Title");
+/// let minimap = pg.create_canvas(200, 150).unwrap();
+/// loop {
+///     pg.canvas(Some(minimap));
+///     pg.clear(Color::BLACK);
+///     pg.fill_color(Color::RED);
+///     pg.circle((100., 75.), 5.);
+///     pg.canvas(None);
+///
+///     pg.texture((600., 0.), minimap.texture(), 200., 150.);
+///     pg.update();
+/// }
+/// ```
+///
+/// [`Window::create_canvas`]: crate::window::Window::create_canvas
+/// [`Window::canvas`]: crate::window::Window::canvas
+/// [`Window::clear`]: crate::window::Window::clear
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Canvas {
+    texture: Texture,
+}
+
+impl Canvas {
+    /// Wrap the store slot `texture`, which has to hold a canvas (See [`Assets::add_canvas`]).
+    ///
+    /// [`Assets::add_canvas`]: crate::assets::Assets::add_canvas
+    pub(crate) fn new(texture: Texture) -> Self {
+        Self { texture }
+    }
+
+    /// The content of the canvas as a texture, to be drawn with functions like [`Window::texture`].
+    /// The texture shows everything drawn to the canvas up to where it is drawn.
+    ///
+    /// [`Window::texture`]: crate::window::Window::texture
+    pub fn texture(&self) -> Texture {
+        self.texture
+    }
+
+    /// The width of the canvas in pixels.
+    pub fn width(&self) -> u32 {
+        self.texture.width()
+    }
+
+    /// The height of the canvas in pixels.
+    pub fn height(&self) -> u32 {
+        self.texture.height()
+    }
+}
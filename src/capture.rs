@@ -0,0 +1,197 @@
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    path::Path,
+};
+
+use sfml::graphics::Image;
+
+use crate::error::{check_image_save_extension, Error};
+
+/// The file name for frame number `frame` from `path`, like for Processing's `saveFrame`:
+/// The first run of `#` in `path` is replaced by the frame number,
+/// padded with leading zeros to the length of the run.
+/// E.g. `"frame-####.png"` becomes `"frame-0042.png"` for frame `42`.
+/// Paths without `#` are returned unchanged.
+pub fn frame_file_name(path: &str, frame: u64) -> String {
+    match path.find('#') {
+        Some(start) => {
+            let len = path[start..].chars().take_while(|&c| c == '#').count();
+            format!(
+                "{}{:0len$}{}",
+                &path[..start],
+                frame,
+                &path[start + len..],
+                len = len
+            )
+        }
+        None => path.to_string(),
+    }
+}
+
+/// The duration of frame number `frame` of a GIF recorded at `fps` frames per second,
+/// in the hundredths of a second GIFs store durations in.
+/// The durations are rounded such that the total duration stays right.
+/// Since many viewers play frames without a duration at their own speed,
+/// each frame lasts at least a hundredth of a second.
+fn gif_delay(fps: f32, frame: u64) -> u16 {
+    let at = |n: u64| (n as f64 * 100. / fps as f64).round();
+    (at(frame + 1) - at(frame)).clamp(1., u16::MAX as f64) as u16
+}
+
+/// Where the frames of a [`Recording`] go.
+enum RecordingOutput {
+    /// An animated GIF, written frame by frame.
+    Gif(gif::Encoder<BufWriter<File>>),
+    /// A numbered sequence of image files, with the path containing a run of `#` (See [`frame_file_name`]).
+    Sequence(String),
+}
+
+/// A recording of frames from a window, started with [`Window::start_recording`].
+///
+/// [`Window::start_recording`]: crate::window::Window::start_recording
+pub struct Recording {
+    output: RecordingOutput,
+    path: String,
+    fps: f32,
+    frames: u64,
+}
+
+impl Recording {
+    /// Start a recording of frames of width `width` and height `height` at `fps` frames per second to `path`,
+    /// as an animated GIF if `path` ends in `.gif`, and as a sequence of image files otherwise.
+    /// For a sequence of image files, frame numbers are inserted before the file extension
+    /// if `path` does not contain a run of `#`.
+    /// Returns an error if `fps` is not positive.
+    pub fn new(
+        path: &str,
+        fps: f32,
+        width: u32,
+        height: u32,
+    ) -> Result<Self, Error> {
+        if !(fps > 0. && fps.is_finite()) {
+            return Err(Error::InvalidFrameRate);
+        }
+        let is_gif = Path::new(path)
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("gif"));
+        let output = if is_gif {
+            let (w, h) = match (u16::try_from(width), u16::try_from(height)) {
+                (Ok(w), Ok(h)) => (w, h),
+                _ => return Err(Error::InvalidSize { width, height }),
+            };
+            let save_error = || Error::Save(path.to_string());
+            let file = File::create(path).map_err(|_| save_error())?;
+            let mut encoder =
+                gif::Encoder::new(BufWriter::new(file), w, h, &[])
+                    .map_err(|_| save_error())?;
+            encoder
+                .set_repeat(gif::Repeat::Infinite)
+                .map_err(|_| save_error())?;
+            RecordingOutput::Gif(encoder)
+        } else {
+            check_image_save_extension(path)?;
+            let sequence = if path.contains('#') {
+                path.to_string()
+            } else {
+                let p = Path::new(path);
+                let stem = p.with_extension("");
+                let ext = p.extension().unwrap_or_default();
+                format!(
+                    "{}-#####.{}",
+                    stem.to_string_lossy(),
+                    ext.to_string_lossy()
+                )
+            };
+            RecordingOutput::Sequence(sequence)
+        };
+        Ok(Self {
+            output,
+            path: path.to_string(),
+            fps,
+            frames: 0,
+        })
+    }
+
+    /// The time that passes between two recorded frames in seconds.
+    pub fn timestep(&self) -> f32 {
+        1. / self.fps
+    }
+
+    /// Add `image` as the next frame of the recording.
+    pub fn add_frame(&mut self, image: &Image) -> Result<(), Error> {
+        match &mut self.output {
+            RecordingOutput::Gif(encoder) => {
+                let size = image.size();
+                let mut pixels = image.pixel_data().to_vec();
+                let mut frame = gif::Frame::from_rgba_speed(
+                    size.x as u16,
+                    size.y as u16,
+                    &mut pixels,
+                    10,
+                );
+                frame.delay = gif_delay(self.fps, self.frames);
+                encoder
+                    .write_frame(&frame)
+                    .map_err(|_| Error::Save(self.path.clone()))?;
+            }
+            RecordingOutput::Sequence(sequence) => {
+                let path = frame_file_name(sequence, self.frames);
+                if !image.save_to_file(&path) {
+                    return Err(Error::Save(path));
+                }
+            }
+        }
+        self.frames += 1;
+        Ok(())
+    }
+
+    /// Finish the recording, completing the GIF file if recording to one.
+    pub fn finish(self) -> Result<(), Error> {
+        if let RecordingOutput::Gif(encoder) = self.output {
+            encoder
+                .into_inner()
+                .and_then(|mut w| w.flush())
+                .map_err(|_| Error::Save(self.path))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frame_file_names() {
+        assert_eq!(frame_file_name("frame-####.png", 42), "frame-0042.png");
+        assert_eq!(frame_file_name("#.png", 1234), "1234.png");
+        assert_eq!(frame_file_name("a-##-##.png", 7), "a-07-##.png");
+        assert_eq!(frame_file_name("frame.png", 7), "frame.png");
+    }
+
+    #[test]
+    fn gif_delays_keep_the_total_duration() {
+        // 30 fps does not divide a second into whole hundredths
+        let delays: Vec<_> = (0..3).map(|n| gif_delay(30., n)).collect();
+        assert_eq!(delays, [3, 4, 3]);
+        let total: u64 = (0..30).map(|n| gif_delay(30., n) as u64).sum();
+        assert_eq!(total, 100);
+        assert!((0..10).all(|n| gif_delay(25., n) == 4));
+    }
+
+    #[test]
+    fn gif_delays_are_never_zero() {
+        assert!((0..100).all(|n| gif_delay(150., n) == 1));
+        assert!((0..100).all(|n| gif_delay(1000., n) == 1));
+        assert_eq!(gif_delay(0.001, 0), u16::MAX);
+    }
+
+    #[test]
+    fn frame_rate_has_to_be_positive() {
+        for fps in [0., -30., f32::NAN, f32::INFINITY] {
+            let recording = Recording::new("frame.png", fps, 100, 100);
+            assert!(recording.err() == Some(Error::InvalidFrameRate));
+        }
+    }
+}
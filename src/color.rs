@@ -82,6 +82,13 @@ impl Color {
         self.sfml_color.alpha()
     }
 
+    /// The color with it's red, green and blue components multiplied by it's alpha,
+    /// as it is stored in canvases that are drawn to with alpha blending.
+    pub(crate) fn premultiplied(self) -> Self {
+        let a = self.alpha();
+        self * Color::rgba(a, a, a, 255)
+    }
+
     pub const TRANSPARENT: Color = Color::rgba(0x00, 0x00, 0x00, 0);
     pub const BLACK: Color = Color::rgb(0x00, 0x00, 0x00);
     pub const WHITE: Color = Color::rgb(0xFF, 0xFF, 0xFF);
@@ -110,3 +117,17 @@ impl From<(u8, u8, u8, u8)> for Color {
         Self::rgba(rgb.0, rgb.1, rgb.2, rgb.3)
     }
 }
+
+/// Multiply two colors component-wise, as if each component ranged from 0 to 1,
+/// like a texture is multiplied with it's tint (See [`Window::tint`]).
+///
+/// [`Window::tint`]: crate::window::Window::tint
+impl std::ops::Mul for Color {
+    type Output = Color;
+
+    fn mul(self, other: Color) -> Color {
+        Color {
+            sfml_color: self.sfml_color * other.sfml_color,
+        }
+    }
+}
@@ -0,0 +1,300 @@
+//! Functions for evaluating points and tangents on Bézier curves and Catmull-Rom splines,
+//! like the ones drawn with [`Window::bezier`], [`Window::quadratic_bezier`] and [`Window::curve`].
+//!
+//! [`Window::bezier`]: crate::window::Window::bezier
+//! [`Window::quadratic_bezier`]: crate::window::Window::quadratic_bezier
+//! [`Window::curve`]: crate::window::Window::curve
+
+type Point = (f32, f32);
+
+fn lerp(a: Point, b: Point, t: f32) -> Point {
+    (a.0 + (b.0 - a.0) * t, a.1 + (b.1 - a.1) * t)
+}
+
+/// The point at `t` (between 0 and 1) on the cubic Bézier curve
+/// from `p0` to `p3` with the control points `p1` and `p2`.
+///
+/// # Examples
+/// ```
+/// # use pronto_graphics::bezier_point;
+/// let middle = bezier_point((0., 0.), (0., 100.), (100., 100.), (100., 0.), 0.5);
+/// assert_eq!(middle, (50., 75.));
+/// ```
+pub fn bezier_point(
+    p0: Point,
+    p1: Point,
+    p2: Point,
+    p3: Point,
+    t: f32,
+) -> Point {
+    let u = 1. - t;
+    let (a, b, c, d) = (u * u * u, 3. * u * u * t, 3. * u * t * t, t * t * t);
+    (
+        a * p0.0 + b * p1.0 + c * p2.0 + d * p3.0,
+        a * p0.1 + b * p1.1 + c * p2.1 + d * p3.1,
+    )
+}
+
+/// The tangent (i.e. the derivative) at `t` (between 0 and 1) of the cubic Bézier curve
+/// from `p0` to `p3` with the control points `p1` and `p2`.
+/// The tangent is not normalized.
+pub fn bezier_tangent(
+    p0: Point,
+    p1: Point,
+    p2: Point,
+    p3: Point,
+    t: f32,
+) -> Point {
+    let u = 1. - t;
+    let (a, b, c) = (3. * u * u, 6. * u * t, 3. * t * t);
+    (
+        a * (p1.0 - p0.0) + b * (p2.0 - p1.0) + c * (p3.0 - p2.0),
+        a * (p1.1 - p0.1) + b * (p2.1 - p1.1) + c * (p3.1 - p2.1),
+    )
+}
+
+/// The point at `t` (between 0 and 1) on the quadratic Bézier curve
+/// from `p0` to `p2` with the control point `p1`.
+pub fn quadratic_bezier_point(
+    p0: Point,
+    p1: Point,
+    p2: Point,
+    t: f32,
+) -> Point {
+    lerp(lerp(p0, p1, t), lerp(p1, p2, t), t)
+}
+
+/// The tangent (i.e. the derivative) at `t` (between 0 and 1) of the quadratic Bézier curve
+/// from `p0` to `p2` with the control point `p1`.
+/// The tangent is not normalized.
+pub fn quadratic_bezier_tangent(
+    p0: Point,
+    p1: Point,
+    p2: Point,
+    t: f32,
+) -> Point {
+    let u = 1. - t;
+    (
+        2. * u * (p1.0 - p0.0) + 2. * t * (p2.0 - p1.0),
+        2. * u * (p1.1 - p0.1) + 2. * t * (p2.1 - p1.1),
+    )
+}
+
+/// The control points of the cubic Bézier curve that is equal to the
+/// segment from `p1` to `p2` of the Catmull-Rom spline through `p0`, `p1`, `p2` and `p3`.
+fn catmull_rom_to_bezier(
+    p0: Point,
+    p1: Point,
+    p2: Point,
+    p3: Point,
+) -> (Point, Point, Point, Point) {
+    (
+        p1,
+        (p1.0 + (p2.0 - p0.0) / 6., p1.1 + (p2.1 - p0.1) / 6.),
+        (p2.0 - (p3.0 - p1.0) / 6., p2.1 - (p3.1 - p1.1) / 6.),
+        p2,
+    )
+}
+
+/// The point at `t` (between 0 and 1) on the segment from `p1` to `p2`
+/// of the Catmull-Rom spline through `p0`, `p1`, `p2` and `p3`.
+pub fn curve_point(
+    p0: Point,
+    p1: Point,
+    p2: Point,
+    p3: Point,
+    t: f32,
+) -> Point {
+    let (b0, b1, b2, b3) = catmull_rom_to_bezier(p0, p1, p2, p3);
+    bezier_point(b0, b1, b2, b3, t)
+}
+
+/// The tangent (i.e. the derivative) at `t` (between 0 and 1) of the segment from `p1` to `p2`
+/// of the Catmull-Rom spline through `p0`, `p1`, `p2` and `p3`.
+/// The tangent is not normalized.
+pub fn curve_tangent(
+    p0: Point,
+    p1: Point,
+    p2: Point,
+    p3: Point,
+    t: f32,
+) -> Point {
+    let (b0, b1, b2, b3) = catmull_rom_to_bezier(p0, p1, p2, p3);
+    bezier_tangent(b0, b1, b2, b3, t)
+}
+
+/// The distance of `p` from the line through `a` and `b`.
+fn line_distance(p: Point, a: Point, b: Point) -> f32 {
+    let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+    let l = (dx * dx + dy * dy).sqrt();
+    if l > 0. {
+        ((p.0 - a.0) * dy - (p.1 - a.1) * dx).abs() / l
+    } else {
+        ((p.0 - a.0).powi(2) + (p.1 - a.1).powi(2)).sqrt()
+    }
+}
+
+/// Append points approximating the cubic Bézier curve from `p0` to `p3` to `points`,
+/// such that the approximation deviates from the curve by at most about `tolerance`.
+/// `p0` itself is not added.
+pub fn flatten_bezier(
+    points: &mut Vec<Point>,
+    p0: Point,
+    p1: Point,
+    p2: Point,
+    p3: Point,
+    tolerance: f32,
+) {
+    fn subdivide(
+        points: &mut Vec<Point>,
+        [p0, p1, p2, p3]: [Point; 4],
+        tolerance: f32,
+        depth: u32,
+    ) {
+        let flat = line_distance(p1, p0, p3).max(line_distance(p2, p0, p3))
+            <= tolerance;
+        if flat || depth >= 16 {
+            points.push(p3);
+        } else {
+            // Split the curve in half with de Casteljau's algorithm
+            let (a, b, c) =
+                (lerp(p0, p1, 0.5), lerp(p1, p2, 0.5), lerp(p2, p3, 0.5));
+            let (d, e) = (lerp(a, b, 0.5), lerp(b, c, 0.5));
+            let m = lerp(d, e, 0.5);
+            subdivide(points, [p0, a, d, m], tolerance, depth + 1);
+            subdivide(points, [m, e, c, p3], tolerance, depth + 1);
+        }
+    }
+    subdivide(points, [p0, p1, p2, p3], tolerance, 0);
+}
+
+/// Append points approximating the Catmull-Rom spline through all of `through` to `points`.
+/// See [`flatten_bezier`] for the meaning of `tolerance`.
+pub fn flatten_curve(
+    points: &mut Vec<Point>,
+    through: &[Point],
+    tolerance: f32,
+) {
+    let n = through.len();
+    if n == 0 {
+        return;
+    }
+    points.push(through[0]);
+    for i in 0..n - 1 {
+        // The spline is extended beyond it's ends by repeating the first and last point.
+        let p0 = through[i.saturating_sub(1)];
+        let p3 = through[(i + 2).min(n - 1)];
+        let (b0, b1, b2, b3) =
+            catmull_rom_to_bezier(p0, through[i], through[i + 1], p3);
+        flatten_bezier(points, b0, b1, b2, b3, tolerance);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(a: Point, b: Point) {
+        assert!(
+            (a.0 - b.0).abs() < 1e-3 && (a.1 - b.1).abs() < 1e-3,
+            "{:?} != {:?}",
+            a,
+            b
+        );
+    }
+
+    const CUBIC: [Point; 4] = [(0., 0.), (0., 100.), (100., 100.), (100., 0.)];
+
+    #[test]
+    fn bezier_endpoints_and_middle() {
+        let [p0, p1, p2, p3] = CUBIC;
+        assert_close(bezier_point(p0, p1, p2, p3, 0.), p0);
+        assert_close(bezier_point(p0, p1, p2, p3, 1.), p3);
+        assert_close(bezier_point(p0, p1, p2, p3, 0.5), (50., 75.));
+    }
+
+    #[test]
+    fn bezier_tangents() {
+        let [p0, p1, p2, p3] = CUBIC;
+        // At the ends the tangent points towards the neighbouring control point
+        assert_close(bezier_tangent(p0, p1, p2, p3, 0.), (0., 300.));
+        assert_close(bezier_tangent(p0, p1, p2, p3, 1.), (0., -300.));
+        assert_close(bezier_tangent(p0, p1, p2, p3, 0.5), (150., 0.));
+    }
+
+    #[test]
+    fn quadratic_bezier() {
+        let (p0, p1, p2) = ((0., 0.), (50., 100.), (100., 0.));
+        assert_close(quadratic_bezier_point(p0, p1, p2, 0.), p0);
+        assert_close(quadratic_bezier_point(p0, p1, p2, 1.), p2);
+        assert_close(quadratic_bezier_point(p0, p1, p2, 0.5), (50., 50.));
+        assert_close(quadratic_bezier_tangent(p0, p1, p2, 0.), (100., 200.));
+        assert_close(quadratic_bezier_tangent(p0, p1, p2, 0.5), (100., 0.));
+    }
+
+    #[test]
+    fn curve_passes_through_inner_points() {
+        let (p0, p1, p2, p3) = ((0., 0.), (10., 20.), (30., 20.), (40., 0.));
+        assert_close(curve_point(p0, p1, p2, p3, 0.), p1);
+        assert_close(curve_point(p0, p1, p2, p3, 1.), p2);
+        // The tangent at an inner point is half the difference of it's neighbours
+        assert_close(curve_tangent(p0, p1, p2, p3, 0.), (15., 10.));
+        assert_close(curve_tangent(p0, p1, p2, p3, 1.), (15., -10.));
+    }
+
+    #[test]
+    fn curve_of_collinear_points_is_straight() {
+        let p = [(0., 0.), (10., 10.), (20., 20.), (30., 30.)];
+        let (x, y) = curve_point(p[0], p[1], p[2], p[3], 0.3);
+        assert!((x - y).abs() < 1e-4);
+        assert!(x > 10. && x < 20.);
+    }
+
+    #[test]
+    fn flatten_bezier_ends_at_the_last_point() {
+        let [p0, p1, p2, p3] = CUBIC;
+        let mut points = Vec::new();
+        flatten_bezier(&mut points, p0, p1, p2, p3, 0.25);
+        assert!(points.len() > 2);
+        assert_close(*points.last().unwrap(), p3);
+        // p0 is not added, and every point lies on the curve
+        assert!(points[0] != p0);
+        for &(x, y) in &points {
+            assert!((0. ..=100.).contains(&x) && (0. ..=75.).contains(&y));
+        }
+    }
+
+    #[test]
+    fn flatten_straight_bezier() {
+        let mut points = Vec::new();
+        flatten_bezier(
+            &mut points,
+            (0., 0.),
+            (10., 0.),
+            (20., 0.),
+            (30., 0.),
+            0.25,
+        );
+        assert_eq!(points, vec![(30., 0.)]);
+    }
+
+    #[test]
+    fn flatten_curve_through_points() {
+        let through = [(0., 0.), (10., 20.), (30., 20.), (40., 0.)];
+        let mut points = Vec::new();
+        flatten_curve(&mut points, &through, 0.25);
+        assert_close(points[0], through[0]);
+        assert_close(*points.last().unwrap(), through[3]);
+        for p in &through {
+            assert!(points
+                .iter()
+                .any(|q| (q.0 - p.0).abs() < 1e-3 && (q.1 - p.1).abs() < 1e-3));
+        }
+
+        let mut empty = Vec::new();
+        flatten_curve(&mut empty, &[], 0.25);
+        assert!(empty.is_empty());
+        flatten_curve(&mut empty, &[(5., 5.)], 0.25);
+        assert_eq!(empty, vec![(5., 5.)]);
+    }
+}
@@ -0,0 +1,155 @@
+use std::fmt;
+
+/// The reasons why loading or creating a texture or font, or saving an image, can fail.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Error {
+    /// There is no file at the given path,
+    /// or no file at the given path inside an embedded asset folder.
+    FileNotFound(String),
+    /// The file is of a type that is not supported,
+    /// like a texture from an SVG file, judging by it's file extension.
+    UnsupportedFormat(String),
+    /// The data could not be decoded, for example because the file is damaged,
+    /// or the data is not of a supported format.
+    Decode(String),
+    /// The pixel data for a texture does not have the expected size of four bytes per pixel.
+    PixelDataSize { expected: usize, actual: usize },
+    /// The region of a texture to update does not lie within the texture.
+    RegionOutOfBounds,
+    /// A texture could not be created with the given size,
+    /// because it is empty or larger than the graphics card supports.
+    InvalidSize { width: u32, height: u32 },
+    /// Textures and fonts can only be used while a window exists on the current thread,
+    /// since they are stored together with the windows (See [`Window`]).
+    ///
+    /// [`Window`]: crate::window::Window
+    StoreNotInitialized,
+    /// The texture or font has been unloaded (See [`Window::unload_texture`]).
+    ///
+    /// [`Window::unload_texture`]: crate::window::Window::unload_texture
+    Unloaded,
+    /// Textures and canvases need a graphics card, so they can not be created for a headless window
+    /// (See [`Window::new_headless`]).
+    ///
+    /// [`Window::new_headless`]: crate::window::Window::new_headless
+    Headless,
+    /// A recording was started with a frame rate that is not a positive number
+    /// (See [`Window::start_recording`]).
+    ///
+    /// [`Window::start_recording`]: crate::window::Window::start_recording
+    InvalidFrameRate,
+    /// An image could not be written to the given path,
+    /// for example because the directory does not exist.
+    Save(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::FileNotFound(path) => write!(f, "file not found: {}", path),
+            Error::UnsupportedFormat(path) => {
+                write!(f, "unsupported file format: {}", path)
+            }
+            Error::Decode(what) => write!(f, "failed to decode {}", what),
+            Error::PixelDataSize { expected, actual } => write!(
+                f,
+                "expected {} bytes of pixel data, got {}",
+                expected, actual
+            ),
+            Error::RegionOutOfBounds => {
+                write!(f, "region does not lie within the texture")
+            }
+            Error::InvalidSize { width, height } => {
+                write!(
+                    f,
+                    "can not create a texture of size {}x{}",
+                    width, height
+                )
+            }
+            Error::StoreNotInitialized => {
+                write!(f, "texture or font store not available")
+            }
+            Error::Unloaded => write!(f, "texture or font has been unloaded"),
+            Error::Headless => write!(f, "not available in a headless window"),
+            Error::InvalidFrameRate => {
+                write!(f, "the frame rate has to be positive")
+            }
+            Error::Save(path) => write!(f, "failed to save {}", path),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// The file extensions of image files that can be loaded as textures.
+const TEXTURE_EXTENSIONS: [&str; 9] = [
+    "bmp", "png", "tga", "jpg", "jpeg", "gif", "psd", "hdr", "pic",
+];
+
+/// The file extensions of image files that images can be saved as.
+const IMAGE_SAVE_EXTENSIONS: [&str; 5] = ["bmp", "png", "tga", "jpg", "jpeg"];
+
+/// The file extensions of font files that can be loaded as fonts.
+const FONT_EXTENSIONS: [&str; 13] = [
+    "ttf", "ttc", "otf", "otc", "cff", "pfa", "pfb", "t42", "pcf", "fnt",
+    "fon", "bdf", "pfr",
+];
+
+fn check_extension(path: &str, extensions: &[&str]) -> Result<(), Error> {
+    match std::path::Path::new(path).extension() {
+        Some(ext)
+            if !extensions.iter().any(|e| ext.eq_ignore_ascii_case(e)) =>
+        {
+            Err(Error::UnsupportedFormat(path.to_string()))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Check that `path` does not name an image file of an unsupported type.
+/// Paths without a file extension are let through.
+pub fn check_texture_extension(path: &str) -> Result<(), Error> {
+    check_extension(path, &TEXTURE_EXTENSIONS)
+}
+
+/// Check that `path` does not name a font file of an unsupported type.
+/// Paths without a file extension are let through.
+pub fn check_font_extension(path: &str) -> Result<(), Error> {
+    check_extension(path, &FONT_EXTENSIONS)
+}
+
+/// Check that `path` names an image file of a type that images can be saved as.
+/// Unlike for loading, paths without a file extension are not let through,
+/// since the file type is chosen by the extension.
+pub fn check_image_save_extension(path: &str) -> Result<(), Error> {
+    if std::path::Path::new(path).extension().is_none() {
+        return Err(Error::UnsupportedFormat(path.to_string()));
+    }
+    check_extension(path, &IMAGE_SAVE_EXTENSIONS)
+}
+
+/// Check that there is a file at `path`.
+pub fn check_file_exists(path: &str) -> Result<(), Error> {
+    if std::path::Path::new(path).is_file() {
+        Ok(())
+    } else {
+        Err(Error::FileNotFound(path.to_string()))
+    }
+}
+
+/// Check that `pixels` holds four bytes for each of `width` times `height` pixels.
+pub fn check_pixel_data(
+    pixels: &[u8],
+    width: u32,
+    height: u32,
+) -> Result<(), Error> {
+    let expected = width as usize * height as usize * 4;
+    if pixels.len() == expected {
+        Ok(())
+    } else {
+        Err(Error::PixelDataSize {
+            expected,
+            actual: pixels.len(),
+        })
+    }
+}
@@ -1,100 +1,79 @@
-//! Not my finest bout of Rust coding, but the combination of storing a file
-//! inside the library's binary with [`RustEmbed`] and SFML's
-//! [`Font::from_memory`] function being a tad picky, some hackery was required.
-//! Though perhaps this could be done a bit cleaner at some point.
+//! The default font is stored inside the library's binary with [`RustEmbed`].
+//! Since SFML's [`SfmlFont::from_memory`] does not copy the font data,
+//! fonts loaded from memory are kept together with their data (See [`StoredFont`]).
 
 use rust_embed::RustEmbed;
 use sfml::{graphics::Font as SfmlFont, SfBox};
 
-//
-//
-// ---------------- Default Font ----------------
+use crate::{
+    assets::with_assets,
+    error::{check_file_exists, check_font_extension, Error},
+};
 
 #[derive(RustEmbed)]
 #[folder = "res/fonts/"]
 struct DefaultFontFile;
-static mut DEFAULT_FONT_BINARY_DATA: Option<Vec<u8>> = None;
-static mut DEFAULT_FONT: Option<SfBox<SfmlFont>> = None;
-static mut TRIED_TO_LOAD_DEFAULT_FONT: bool = false;
 
-pub fn init_default_font() {
-    unsafe {
-        if !TRIED_TO_LOAD_DEFAULT_FONT {
-            TRIED_TO_LOAD_DEFAULT_FONT = true;
-
-            // We have to create a static copy of the font data in memory,
-            // because otherwise [`Font::from_memory`] will quietly fail for unknown reasons.
-            DEFAULT_FONT_BINARY_DATA =
-                DefaultFontFile::get("ProcessingSansPro-Regular.ttf")
-                    .map(|binary| binary.data.to_vec());
-
-            // Try to initialize default font once. If this fails, we just don't show any text,
-            // unless the user has loaded their own font.
-            // But that should never happen unless there is a bug in the code, since the default
-            // font is stored inside the library's binary.
-            DEFAULT_FONT = DEFAULT_FONT_BINARY_DATA
-                .as_ref()
-                .and_then(|bin| SfmlFont::from_memory(&bin[..])).or_else(|| {
-                    eprintln!("Failed to load default font. This should not have happened.");
-                    None
-                });
-        }
-    }
-}
-
-pub fn default_font() -> Option<&'static SfBox<SfmlFont>> {
-    unsafe { DEFAULT_FONT.as_ref() }
+/// A font, together with the data it was loaded from, if it was loaded from memory.
+pub struct StoredFont {
+    // Declared before `data`, so that the font is dropped first.
+    pub font: SfBox<SfmlFont>,
+    _data: Option<Vec<u8>>,
 }
 
-//
-//
-// ---------------- Font Store ----------------
-
-/// A global static array containing all fonts that have been loaded during the runtime of the program.
-/// Should not be accesses directly outside this module.
-/// # Excuses
-/// See [`crate::texture::TEXTURE_STORE`].
-static mut FONT_STORE: Option<Vec<SfBox<SfmlFont>>> = None;
-pub fn init_font_store() {
-    unsafe {
-        if FONT_STORE.is_none() {
-            FONT_STORE = Some(Vec::new());
-        }
+impl StoredFont {
+    pub fn from_file(path: &str) -> Result<Self, Error> {
+        check_file_exists(path)?;
+        check_font_extension(path)?;
+        Ok(Self {
+            font: SfmlFont::from_file(path)
+                .ok_or_else(|| Error::Decode(format!("font '{}'", path)))?,
+            _data: None,
+        })
     }
-}
 
-pub fn font_store(font: Font) -> Option<&'static SfBox<SfmlFont>> {
-    unsafe {
-        if let Some(fonts) = &FONT_STORE {
-            Some(&fonts[font.index])
-        } else {
-            None
-        }
+    pub fn from_memory(data: &[u8]) -> Result<Self, Error> {
+        // The font has to be loaded from a copy of the data that is kept alive for as long as the font,
+        // because otherwise [`SfmlFont::from_memory`] will quietly fail or read freed memory.
+        let data = data.to_vec();
+        Ok(Self {
+            font: SfmlFont::from_memory(&data[..])
+                .ok_or_else(|| Error::Decode(String::from("font data")))?,
+            _data: Some(data),
+        })
     }
 }
 
-pub fn font_store_add(font: SfBox<SfmlFont>) -> Option<Font> {
-    unsafe {
-        if let Some(fonts) = &mut FONT_STORE {
-            fonts.push(font);
-            Some(Font {
-                index: fonts.len() - 1,
-            })
-        } else {
-            None
-        }
-    }
+/// Load the default font stored inside the library's binary.
+pub fn load_default_font() -> Result<StoredFont, Error> {
+    // If this fails, we just don't show any text, unless the user has loaded their own font.
+    // But that should never happen unless there is a bug in the code, since the default
+    // font is stored inside the library's binary.
+    const NAME: &str = "ProcessingSansPro-Regular.ttf";
+    DefaultFontFile::get(NAME)
+        .ok_or_else(|| Error::FileNotFound(NAME.to_string()))
+        .and_then(|binary| StoredFont::from_memory(&binary.data))
+        .inspect_err(|e| {
+            eprintln!(
+                "Failed to load default font ({}). This should not have happened.",
+                e
+            )
+        })
 }
 
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub struct Font {
     pub index: usize,
+    /// Tells apart fonts that were loaded one after another into the same slot,
+    /// after the earlier one was unloaded with [`Window::unload_font`].
+    ///
+    /// [`Window::unload_font`]: crate::window::Window::unload_font
+    pub generation: u32,
 }
 
 impl Font {
     pub fn name(&self) -> String {
-        font_store(*self)
-            .map(|f| f.info().family)
+        with_assets(|assets| assets.font(*self).map(|f| f.info().family))
             .unwrap_or_else(|| String::from(""))
     }
 }
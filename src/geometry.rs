@@ -0,0 +1,529 @@
+//! Helpers for turning shapes into triangles and lines that can be handed to the GPU.
+
+/// Twice the signed area of the polygon `points`.
+/// Positive for clockwise polygons (in screen coordinates, where the y axis points down).
+fn signed_area(points: &[(f32, f32)]) -> f32 {
+    let n = points.len();
+    (0..n)
+        .map(|i| {
+            let (a, b) = (points[i], points[(i + 1) % n]);
+            a.0 * b.1 - b.0 * a.1
+        })
+        .sum()
+}
+
+fn cross(o: (f32, f32), a: (f32, f32), b: (f32, f32)) -> f32 {
+    (a.0 - o.0) * (b.1 - o.1) - (a.1 - o.1) * (b.0 - o.0)
+}
+
+fn in_triangle(
+    p: (f32, f32),
+    a: (f32, f32),
+    b: (f32, f32),
+    c: (f32, f32),
+) -> bool {
+    cross(a, b, p) >= 0. && cross(b, c, p) >= 0. && cross(c, a, p) >= 0.
+}
+
+/// Whether the polygon `points`, visited in the order of `indices`, only ever turns clockwise.
+fn is_convex(points: &[(f32, f32)], indices: &[usize]) -> bool {
+    let n = indices.len();
+    (0..n).all(|i| {
+        let a = points[indices[i]];
+        let b = points[indices[(i + 1) % n]];
+        let c = points[indices[(i + 2) % n]];
+        cross(a, b, c) >= 0.
+    })
+}
+
+/// Split the simple (possibly concave) polygon `points` into triangles by ear clipping.
+/// Returns a list of indices into `points`, where every three consecutive indices form one triangle.
+///
+/// Self-intersecting polygons do not have a well defined inside,
+/// but will still be turned into _some_ set of triangles.
+pub fn triangulate(points: &[(f32, f32)]) -> Vec<usize> {
+    if points.len() < 3 {
+        return Vec::new();
+    }
+
+    // Work on a clockwise list of indices, so that every ear has a positive cross product.
+    let mut indices: Vec<usize> = (0..points.len()).collect();
+    if signed_area(points) < 0. {
+        indices.reverse();
+    }
+
+    // Convex polygons, like ellipses, can be split up much faster.
+    if is_convex(points, &indices) {
+        return (1..indices.len() - 1)
+            .flat_map(|i| [indices[0], indices[i], indices[i + 1]])
+            .collect();
+    }
+
+    let mut triangles = Vec::with_capacity(3 * (points.len() - 2));
+    while indices.len() > 3 {
+        let n = indices.len();
+        let ear = (0..n).find(|&i| {
+            let a = points[indices[(i + n - 1) % n]];
+            let b = points[indices[i]];
+            let c = points[indices[(i + 1) % n]];
+            cross(a, b, c) > 0.
+                && indices.iter().all(|&j| {
+                    let p = points[j];
+                    p == a || p == b || p == c || !in_triangle(p, a, b, c)
+                })
+        });
+
+        // If there is no proper ear, the polygon is degenerate or self-intersecting.
+        // Clip an arbitrary vertex, so that we always make progress.
+        let i = ear.unwrap_or(0);
+        triangles.push(indices[(i + n - 1) % n]);
+        triangles.push(indices[i]);
+        triangles.push(indices[(i + 1) % n]);
+        indices.remove(i);
+    }
+    triangles.extend(indices);
+
+    triangles
+}
+
+/// How the ends of thick lines are drawn.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum LineCap {
+    /// The line ends exactly at it's end points.
+    Butt,
+    /// The line is extended by a half circle around it's end points.
+    Round,
+    /// The line is extended by half the line width beyond it's end points.
+    Square,
+}
+
+/// How the corners between consecutive segments of thick lines are drawn.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum LineJoin {
+    /// The outer edges of the segments are extended until they meet in a sharp point.
+    /// Very sharp corners fall back to [`LineJoin::Bevel`].
+    Miter,
+    /// The corner is rounded off with a circle around the corner point.
+    Round,
+    /// The corner is cut off with a straight line.
+    Bevel,
+}
+
+/// The maximum ratio between the length of a miter and half the line width,
+/// beyond which a miter join is drawn as a bevel join instead.
+const MITER_LIMIT: f32 = 4.;
+
+fn add(a: (f32, f32), b: (f32, f32)) -> (f32, f32) {
+    (a.0 + b.0, a.1 + b.1)
+}
+
+fn mul(a: (f32, f32), s: f32) -> (f32, f32) {
+    (a.0 * s, a.1 * s)
+}
+
+fn normalize(a: (f32, f32)) -> (f32, f32) {
+    let l = (a.0 * a.0 + a.1 * a.1).sqrt();
+    if l > 0. {
+        (a.0 / l, a.1 / l)
+    } else {
+        (0., 0.)
+    }
+}
+
+/// The number of segments needed to approximate a full circle of radius `radius`
+/// (in pixels) smoothly, without wasting vertices on small circles.
+pub fn circle_segments(radius: f32) -> usize {
+    // Choose the segment count such that the maximum distance between the
+    // polygon and the true circle stays below a quarter pixel.
+    let tolerance = 0.25;
+    if radius <= tolerance {
+        return 8;
+    }
+    let angle = 2. * (1. - tolerance / radius).acos();
+    ((std::f32::consts::TAU / angle).ceil() as usize).clamp(8, 512)
+}
+
+/// Add a triangle fan around `center` to `triangles`, sweeping the vector `from` by `sweep` radians.
+fn fan(
+    triangles: &mut Vec<(f32, f32)>,
+    center: (f32, f32),
+    from: (f32, f32),
+    sweep: f32,
+) {
+    let radius = (from.0 * from.0 + from.1 * from.1).sqrt();
+    let steps = ((circle_segments(radius) as f32 * sweep.abs()
+        / std::f32::consts::TAU)
+        .ceil() as usize)
+        .max(1);
+    let start = from.1.atan2(from.0);
+    let point = |i: usize| {
+        let a = start + sweep * i as f32 / steps as f32;
+        add(center, (radius * a.cos(), radius * a.sin()))
+    };
+    for i in 0..steps {
+        triangles.extend([center, point(i), point(i + 1)]);
+    }
+}
+
+/// Turn the line through `points` into triangles, so that it can be drawn with a width of `width`.
+/// If `closed` is set, the last point is connected back to the first one.
+/// Returns a list of vertices, where every three consecutive vertices form one triangle.
+pub fn stroke(
+    points: &[(f32, f32)],
+    closed: bool,
+    width: f32,
+    cap: LineCap,
+    join: LineJoin,
+) -> Vec<(f32, f32)> {
+    let hw = width / 2.;
+
+    let mut points: Vec<(f32, f32)> = points.to_vec();
+    points.dedup();
+    if closed && points.len() > 1 && points.first() == points.last() {
+        points.pop();
+    }
+    let n = points.len();
+    if n < 2 || hw <= 0. {
+        return Vec::new();
+    }
+
+    let segment_count = if closed { n } else { n - 1 };
+    let direction = |i: usize| {
+        let (a, b) = (points[i % n], points[(i + 1) % n]);
+        normalize((b.0 - a.0, b.1 - a.1))
+    };
+    let normal = |d: (f32, f32)| (-d.1, d.0);
+
+    let mut triangles = Vec::new();
+
+    for i in 0..segment_count {
+        let d = direction(i);
+        let mut a = points[i];
+        let mut b = points[(i + 1) % n];
+        if !closed && cap == LineCap::Square {
+            if i == 0 {
+                a = add(a, mul(d, -hw));
+            }
+            if i == segment_count - 1 {
+                b = add(b, mul(d, hw));
+            }
+        }
+        let o = mul(normal(d), hw);
+        let (a0, b0) = (add(a, o), add(b, o));
+        let (a1, b1) = (add(a, mul(o, -1.)), add(b, mul(o, -1.)));
+        triangles.extend([a0, b0, b1, a0, b1, a1]);
+    }
+
+    // Joins between consecutive segments
+    let joins = if closed { 0..n } else { 1..n - 1 };
+    for i in joins {
+        let d0 = direction((i + n - 1) % n);
+        let d1 = direction(i);
+        let turn = d0.0 * d1.1 - d0.1 * d1.0;
+        if turn == 0. {
+            continue;
+        }
+        // The outer side of the corner is opposite to the direction of the turn.
+        let side = if turn > 0. { -hw } else { hw };
+        let p = points[i];
+        let n0 = mul(normal(d0), side);
+        let n1 = mul(normal(d1), side);
+
+        match join {
+            LineJoin::Round => {
+                let sweep = turn.atan2(d0.0 * d1.0 + d0.1 * d1.1);
+                fan(&mut triangles, p, n0, sweep);
+            }
+            LineJoin::Miter | LineJoin::Bevel => {
+                let m = normalize(add(n0, n1));
+                let cos = (m.0 * n0.0 + m.1 * n0.1) / hw;
+                if join == LineJoin::Miter && cos > 1. / MITER_LIMIT {
+                    let miter = add(p, mul(m, hw / cos));
+                    triangles.extend([
+                        p,
+                        add(p, n0),
+                        miter,
+                        p,
+                        miter,
+                        add(p, n1),
+                    ]);
+                } else {
+                    triangles.extend([p, add(p, n0), add(p, n1)]);
+                }
+            }
+        }
+    }
+
+    // Caps at the ends of open lines
+    if !closed && cap == LineCap::Round {
+        let d0 = direction(0);
+        fan(
+            &mut triangles,
+            points[0],
+            mul(normal(d0), hw),
+            std::f32::consts::PI,
+        );
+        let d1 = direction(n - 2);
+        fan(
+            &mut triangles,
+            points[n - 1],
+            mul(normal(d1), -hw),
+            std::f32::consts::PI,
+        );
+    }
+
+    triangles
+}
+
+/// Turn the outline of the polygon `points` into triangles of thickness `thickness`,
+/// which lie entirely outside of the polygon, the same way SFML draws the outline of it's shapes.
+/// Returns a list of vertices, where every three consecutive vertices form one triangle.
+pub fn outline(points: &[(f32, f32)], thickness: f32) -> Vec<(f32, f32)> {
+    let mut points: Vec<(f32, f32)> = points.to_vec();
+    points.dedup();
+    if points.len() > 1 && points.first() == points.last() {
+        points.pop();
+    }
+    let n = points.len();
+    if n < 3 || thickness <= 0. {
+        return Vec::new();
+    }
+
+    // For clockwise polygons, the outside is to the left of each edge.
+    let side = if signed_area(&points) < 0. { 1. } else { -1. };
+    let outward = |i: usize| {
+        let (a, b) = (points[i % n], points[(i + 1) % n]);
+        let d = normalize((b.0 - a.0, b.1 - a.1));
+        (-d.1 * side, d.0 * side)
+    };
+
+    let offset: Vec<(f32, f32)> = (0..n)
+        .map(|i| {
+            let (o0, o1) = (outward(i + n - 1), outward(i));
+            let m = normalize(add(o0, o1));
+            let cos = (m.0 * o0.0 + m.1 * o0.1).max(1. / MITER_LIMIT);
+            add(points[i], mul(m, thickness / cos))
+        })
+        .collect();
+
+    (0..n)
+        .flat_map(|i| {
+            let j = (i + 1) % n;
+            [
+                points[i], offset[i], offset[j], points[i], offset[j],
+                points[j],
+            ]
+        })
+        .collect()
+}
+
+/// The points of an elliptic arc around `center` with radii `radius`,
+/// from angle `start` to angle `end` (in radians, clockwise from the positive x axis).
+/// `scale` is the factor by which the arc is scaled on the screen,
+/// which is used to choose a sensible number of points.
+pub fn arc_points(
+    center: (f32, f32),
+    radius: (f32, f32),
+    start: f32,
+    end: f32,
+    scale: f32,
+) -> Vec<(f32, f32)> {
+    let sweep = end - start;
+    let segments = circle_segments(radius.0.max(radius.1) * scale) as f32
+        * sweep.abs()
+        / std::f32::consts::TAU;
+    let steps = (segments.ceil() as usize).max(1);
+    (0..=steps)
+        .map(|i| {
+            let a = start + sweep * i as f32 / steps as f32;
+            (center.0 + radius.0 * a.cos(), center.1 + radius.1 * a.sin())
+        })
+        .collect()
+}
+
+/// The points of an ellipse around `center` with radii `radius`.
+/// See [`arc_points`] for the meaning of `scale`.
+pub fn ellipse_points(
+    center: (f32, f32),
+    radius: (f32, f32),
+    scale: f32,
+) -> Vec<(f32, f32)> {
+    let mut points =
+        arc_points(center, radius, 0., std::f32::consts::TAU, scale);
+    points.pop();
+    points
+}
+
+/// The points of a rectangle with it's top left at `pos`, of size `(width, height)`,
+/// and with it's corners rounded off with a radius of `radius`.
+/// See [`arc_points`] for the meaning of `scale`.
+pub fn rounded_rectangle_points(
+    pos: (f32, f32),
+    width: f32,
+    height: f32,
+    radius: f32,
+    scale: f32,
+) -> Vec<(f32, f32)> {
+    use std::f32::consts::{FRAC_PI_2, PI};
+
+    let r = radius.clamp(0., width.abs().min(height.abs()) / 2.);
+    let (x0, y0) = (pos.0 + r, pos.1 + r);
+    let (x1, y1) = (pos.0 + width - r, pos.1 + height - r);
+    [
+        ((x0, y0), PI),
+        ((x1, y0), -FRAC_PI_2),
+        ((x1, y1), 0.),
+        ((x0, y1), FRAC_PI_2),
+    ]
+    .iter()
+    .flat_map(|&(center, start)| {
+        arc_points(center, (r, r), start, start + FRAC_PI_2, scale)
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The total area of the triangles `triangles`, where every three consecutive vertices form one triangle.
+    fn area(triangles: &[(f32, f32)]) -> f32 {
+        triangles
+            .chunks_exact(3)
+            .map(|t| cross(t[0], t[1], t[2]).abs() / 2.)
+            .sum()
+    }
+
+    fn triangulated(points: &[(f32, f32)]) -> Vec<(f32, f32)> {
+        triangulate(points).iter().map(|i| points[*i]).collect()
+    }
+
+    fn assert_close(a: f32, b: f32, tolerance: f32) {
+        assert!((a - b).abs() <= tolerance, "{} is not close to {}", a, b);
+    }
+
+    #[test]
+    fn triangulate_needs_three_points() {
+        assert!(triangulate(&[]).is_empty());
+        assert!(triangulate(&[(0., 0.), (1., 1.)]).is_empty());
+        assert_eq!(triangulate(&[(0., 0.), (1., 0.), (0., 1.)]).len(), 3);
+    }
+
+    #[test]
+    fn triangulate_convex_polygon() {
+        let square = [(0., 0.), (2., 0.), (2., 2.), (0., 2.)];
+        let triangles = triangulated(&square);
+        assert_eq!(triangles.len(), 6);
+        assert_close(area(&triangles), 4., 1e-5);
+    }
+
+    #[test]
+    fn triangulate_concave_polygon() {
+        // An L shape, which a triangle fan from the first corner would get wrong
+        let l = [(0., 0.), (2., 0.), (2., 1.), (1., 1.), (1., 2.), (0., 2.)];
+        for points in [l.to_vec(), l.iter().rev().copied().collect()] {
+            let triangles = triangulated(&points);
+            assert_eq!(triangles.len(), 3 * (points.len() - 2));
+            assert_close(area(&triangles), 3., 1e-5);
+            // No triangle covers the notch of the L
+            for t in triangles.chunks_exact(3) {
+                assert!(!in_triangle((1.5, 1.5), t[0], t[1], t[2]));
+            }
+        }
+    }
+
+    #[test]
+    fn triangulate_keeps_winding_consistent() {
+        let points = [(0., 0.), (0., 2.), (2., 2.), (3., 1.), (2., 0.)];
+        for t in triangulated(&points).chunks_exact(3) {
+            assert!(cross(t[0], t[1], t[2]) >= 0.);
+        }
+    }
+
+    #[test]
+    fn triangulate_degenerate_polygon_terminates() {
+        let points = [(0., 0.), (1., 0.), (2., 0.), (3., 0.)];
+        assert_eq!(triangulate(&points).len(), 6);
+        assert_close(area(&triangulated(&points)), 0., 1e-5);
+    }
+
+    #[test]
+    fn stroke_needs_two_points_and_a_width() {
+        let cap = LineCap::Butt;
+        let join = LineJoin::Miter;
+        assert!(stroke(&[(0., 0.)], false, 2., cap, join).is_empty());
+        assert!(stroke(&[(1., 1.), (1., 1.)], false, 2., cap, join).is_empty());
+        assert!(stroke(&[(0., 0.), (1., 0.)], false, 0., cap, join).is_empty());
+    }
+
+    #[test]
+    fn stroke_caps() {
+        let line = [(0., 0.), (10., 0.)];
+        let join = LineJoin::Miter;
+        let butt = stroke(&line, false, 2., LineCap::Butt, join);
+        assert_close(area(&butt), 20., 1e-4);
+        // The line lies centered on it's points
+        assert!(butt.iter().all(|p| p.1.abs() <= 1. + 1e-6));
+
+        let square = stroke(&line, false, 2., LineCap::Square, join);
+        assert_close(area(&square), 24., 1e-4);
+
+        let round = stroke(&line, false, 2., LineCap::Round, join);
+        // Round caps are approximated by inscribed polygons, so they are a bit smaller
+        let exact = 20. + std::f32::consts::PI;
+        assert!(area(&round) <= exact);
+        assert_close(area(&round), exact, 0.35);
+    }
+
+    #[test]
+    fn stroke_joins() {
+        // A right angle, where the joins only differ in the outer corner
+        let corner = [(0., 0.), (10., 0.), (10., 10.)];
+        let stroke_area =
+            |join| area(&stroke(&corner, false, 2., LineCap::Butt, join));
+        let (miter, round, bevel) = (
+            stroke_area(LineJoin::Miter),
+            stroke_area(LineJoin::Round),
+            stroke_area(LineJoin::Bevel),
+        );
+        // The two segments cover 40, the miter adds the 1x1 outer corner square,
+        // the bevel half of it, and the round join a quarter circle.
+        assert_close(miter, 41., 1e-3);
+        assert_close(bevel, 40.5, 1e-3);
+        assert!(round > bevel && round <= 40. + std::f32::consts::PI / 4.);
+    }
+
+    #[test]
+    fn sharp_miter_falls_back_to_bevel() {
+        let spike = [(0., 0.), (10., 0.), (0., 0.5)];
+        let miter = stroke(&spike, false, 2., LineCap::Butt, LineJoin::Miter);
+        let bevel = stroke(&spike, false, 2., LineCap::Butt, LineJoin::Bevel);
+        assert_close(area(&miter), area(&bevel), 1e-3);
+        assert!(miter.iter().all(|p| p.0 < 12.));
+    }
+
+    #[test]
+    fn closed_stroke_joins_the_ends() {
+        let square = [(0., 0.), (10., 0.), (10., 10.), (0., 10.)];
+        let open = stroke(&square, false, 2., LineCap::Butt, LineJoin::Miter);
+        let closed = stroke(&square, true, 2., LineCap::Butt, LineJoin::Miter);
+        // 12x12 minus the 8x8 hole, with the overlapping corners counted twice
+        assert_close(area(&closed), 12. * 12. - 8. * 8. + 4., 1e-3);
+        assert!(area(&closed) > area(&open));
+    }
+
+    #[test]
+    fn outline_lies_outside_the_polygon() {
+        let square = [(0., 0.), (10., 0.), (10., 10.), (0., 10.)];
+        for points in [square.to_vec(), square.iter().rev().copied().collect()]
+        {
+            let triangles = outline(&points, 2.);
+            assert_close(area(&triangles), 14. * 14. - 10. * 10., 1e-3);
+            for p in &triangles {
+                let inside = p.0 > 0. && p.0 < 10. && p.1 > 0. && p.1 < 10.;
+                assert!(!inside, "{:?} lies inside the polygon", p);
+            }
+        }
+        assert!(outline(&square, 0.).is_empty());
+    }
+}
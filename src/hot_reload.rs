@@ -0,0 +1,74 @@
+use std::{fs, time::SystemTime};
+
+use crate::{
+    assets::Assets,
+    font::{Font, StoredFont},
+    texture::{texture_from_file, Texture},
+};
+
+/// A texture or font that was loaded from a file.
+#[derive(Clone, Copy, PartialEq)]
+pub enum Asset {
+    Texture(Texture),
+    Font(Font),
+}
+
+/// A file a texture or font was loaded from,
+/// to be loaded again when it changes (See [`Window::hot_reload`]).
+///
+/// [`Window::hot_reload`]: crate::window::Window::hot_reload
+pub struct WatchedFile {
+    path: String,
+    asset: Asset,
+    modified: Option<SystemTime>,
+}
+
+impl WatchedFile {
+    pub fn new(path: &str, asset: Asset) -> Self {
+        Self {
+            path: path.to_string(),
+            asset,
+            modified: modified(path),
+        }
+    }
+
+    /// The texture or font loaded from the file.
+    pub fn asset(&self) -> Asset {
+        self.asset
+    }
+}
+
+/// When the file at `path` was last modified, if that can be found out.
+fn modified(path: &str) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// Load the textures and fonts of all files in `watched` again that have changed since they were last loaded.
+/// Files of textures and fonts that have been unloaded are no longer watched.
+pub fn reload_changed_files(
+    assets: &mut Assets,
+    watched: &mut Vec<WatchedFile>,
+) {
+    watched.retain(|file| match file.asset {
+        Asset::Texture(t) => assets.texture(t).is_some(),
+        Asset::Font(f) => assets.font(f).is_some(),
+    });
+    for file in watched {
+        let modified = modified(&file.path);
+        if modified.is_none() || modified == file.modified {
+            continue;
+        }
+        // Even if loading fails, e.g. because the file is only half written,
+        // it is only tried again once the file changes again.
+        file.modified = modified;
+        let result = match file.asset {
+            Asset::Texture(t) => texture_from_file(&file.path)
+                .map(|new| assets.replace_texture(t, new)),
+            Asset::Font(f) => StoredFont::from_file(&file.path)
+                .map(|new| assets.replace_font(f, new)),
+        };
+        if let Err(e) = result {
+            eprintln!("Failed to reload '{}': {}", file.path, e);
+        }
+    }
+}
@@ -34,16 +34,53 @@
 //! Pronto Graphics is not thread safe, both due to it's own internal structure and the fact it uses
 //! SFML for drawing, which already [isn't thread safe](https://docs.rs/sfml/latest/sfml/index.html#-thread-safety-warning-).
 //! As long as you only use Pronto Graphics in your main thread however, it should be fine to have parallel non-graphics threads.
+//! Loaded textures and fonts are shared by all windows of the thread they were loaded in,
+//! and can not be used in windows of other threads.
 
+mod animation;
+mod assets;
+mod canvas;
+mod capture;
 mod color;
+mod curve;
+mod error;
 mod font;
+mod geometry;
+mod hot_reload;
 mod input;
+mod raster;
 mod render_parameters;
+mod rich_text;
 mod shape;
+mod sprite;
+mod store;
+mod text;
 mod texture;
+mod transform;
 mod window;
+pub use animation::{Animation, AnimationMode};
+pub use canvas::Canvas;
 pub use color::Color;
+pub use curve::{
+    bezier_point, bezier_tangent, curve_point, curve_tangent,
+    quadratic_bezier_point, quadratic_bezier_tangent,
+};
+pub use error::Error;
 pub use font::Font;
-pub use sfml::window::{mouse::Button, Key};
+pub use geometry::{LineCap, LineJoin};
+pub use render_parameters::DrawMode;
+pub use rich_text::{RichText, Span};
+/// Re-exported for embedding asset folders into the program,
+/// to load textures and fonts from with [`Window::load_texture_from_asset`] and [`Window::load_font_from_asset`].
+/// Since the code generated by `#[derive(RustEmbed)]` refers to the `rust_embed` crate,
+/// both have to be in scope, i.e. `use pronto_graphics::{rust_embed, RustEmbed};`.
+pub use rust_embed::{self, RustEmbed};
+pub use sfml::{
+    graphics::{Image, TextStyle},
+    window::{mouse::Button, Key},
+};
+pub use shape::{ArcMode, ShapeMode};
+pub use sprite::{Sprite, SpriteSheet};
+pub use text::{HorizontalAlign, TextOverflow, VerticalAlign};
 pub use texture::Texture;
 pub use window::Window;
@@ -0,0 +1,661 @@
+//! A software rasterizer for drawing render tasks without a display or graphics card,
+//! used by headless windows (See [`Window::new_headless`]).
+//!
+//! [`Window::new_headless`]: crate::window::Window::new_headless
+
+use std::sync::Once;
+
+use crate::{
+    color::Color,
+    geometry::{
+        arc_points, ellipse_points, outline, rounded_rectangle_points, stroke,
+        triangulate, LineCap, LineJoin,
+    },
+    render_parameters::RenderParameterState,
+    shape::{ArcMode, RenderTask, ShapeMode, ShapeVertex, Shapes},
+    transform::Transform,
+};
+
+/// Print a warning that textures and text are not drawn, the first time anything is skipped.
+pub fn warn_not_drawn() {
+    static WARNING: Once = Once::new();
+    WARNING.call_once(|| {
+        eprintln!(
+            "Textures and text can not be drawn in a headless window and are skipped."
+        )
+    });
+}
+
+/// The color between `a` and `b` at `t`, from `a` at `0` to `b` at `1`.
+fn mix(a: Color, b: Color, t: f32) -> Color {
+    let t = if t.is_finite() { t.clamp(0., 1.) } else { 0. };
+    let mix = |f: fn(&Color) -> u8| {
+        (f(&a) as f32 + (f(&b) as f32 - f(&a) as f32) * t).round() as u8
+    };
+    Color::rgba(
+        mix(Color::red),
+        mix(Color::green),
+        mix(Color::blue),
+        mix(Color::alpha),
+    )
+}
+
+/// An image in memory that render tasks can be drawn to in software.
+pub struct Raster {
+    width: u32,
+    height: u32,
+    pixels: Vec<Color>,
+}
+
+impl Raster {
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![Color::BLACK; width as usize * height as usize],
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// The pixels, row by row from the top left.
+    pub fn pixels(&self) -> &[Color] {
+        &self.pixels
+    }
+
+    pub fn clear(&mut self, color: Color) {
+        self.pixels.fill(color);
+    }
+
+    /// Draw `other`, which has to be of the same size, over this raster.
+    /// The colors of `other` are premultiplied by their alpha, as they are after drawing
+    /// onto a transparent raster, so they are not multiplied by it again.
+    pub fn draw_raster(&mut self, other: &Raster) {
+        for (pixel, src) in self.pixels.iter_mut().zip(&other.pixels) {
+            let a = src.alpha() as f32 / 255.;
+            let mix = |src: u8, dst: u8| {
+                (src as f32 + dst as f32 * (1. - a)).round().min(255.) as u8
+            };
+            *pixel = Color::rgba(
+                mix(src.red(), pixel.red()),
+                mix(src.green(), pixel.green()),
+                mix(src.blue(), pixel.blue()),
+                mix(src.alpha(), pixel.alpha()),
+            );
+        }
+    }
+
+    /// Draw the render task `task`, the same way it would be drawn by SFML.
+    /// Textures and text are not drawn, since they live on the graphics card (See [`warn_not_drawn`]).
+    pub fn draw_task(&mut self, task: &RenderTask) {
+        let RenderTask {
+            pos,
+            shape,
+            render_parameter_state: state,
+            transform,
+        } = task;
+        let scale = transform.scale_factor();
+
+        match shape {
+            Shapes::Circle { radius } => {
+                let (center, radius) =
+                    state.ellipse_mode.ellipse(*pos, (*radius, *radius));
+                let points = ellipse_points(center, radius, scale);
+                self.fill_shape(&points, state, transform);
+            }
+            Shapes::Rectangle { width, height } => {
+                let ((x, y), (w, h)) =
+                    state.rect_mode.rectangle(*pos, (*width, *height));
+                let points = [(x, y), (x + w, y), (x + w, y + h), (x, y + h)];
+                self.fill_shape(&points, state, transform);
+            }
+            Shapes::Ellipse { radius_x, radius_y } => {
+                let (center, radius) =
+                    state.ellipse_mode.ellipse(*pos, (*radius_x, *radius_y));
+                let points = ellipse_points(center, radius, scale);
+                self.fill_shape(&points, state, transform);
+            }
+            Shapes::Arc { radius, start, end } => {
+                let (center, (radius_x, radius_y)) =
+                    state.ellipse_mode.ellipse(*pos, (*radius, *radius));
+                let mut points = arc_points(
+                    center,
+                    (radius_x, radius_y),
+                    *start,
+                    *end,
+                    scale,
+                );
+                match state.arc_mode {
+                    ArcMode::Open => {
+                        let fill = RenderParameterState {
+                            outline_thickness: 0.,
+                            ..*state
+                        };
+                        self.fill_shape(&points, &fill, transform);
+
+                        // Like for SFML, stroke along a slightly larger arc,
+                        // so that the outline lies outside the arc.
+                        let t = state.outline_thickness;
+                        let r = (radius_x + t / 2., radius_y + t / 2.);
+                        let points = arc_points(center, r, *start, *end, scale);
+                        let triangles = stroke(
+                            &points,
+                            false,
+                            t,
+                            LineCap::Butt,
+                            LineJoin::Miter,
+                        );
+                        self.fill_triangles(
+                            &triangles,
+                            state.outline_color,
+                            transform,
+                        );
+                    }
+                    ArcMode::Chord => {
+                        self.fill_shape(&points, state, transform)
+                    }
+                    ArcMode::Pie => {
+                        points.push(center);
+                        self.fill_shape(&points, state, transform);
+                    }
+                }
+            }
+            Shapes::RoundedRectangle {
+                width,
+                height,
+                corner_radius,
+            } => {
+                let (top_left, (width, height)) =
+                    state.rect_mode.rectangle(*pos, (*width, *height));
+                let points = rounded_rectangle_points(
+                    top_left,
+                    width,
+                    height,
+                    *corner_radius,
+                    scale,
+                );
+                self.fill_shape(&points, state, transform);
+            }
+            Shapes::Lines { coords } => {
+                for segment in coords.chunks_exact(2) {
+                    self.draw_line(
+                        segment,
+                        false,
+                        state.line_width,
+                        state.line_color,
+                        state,
+                        transform,
+                    );
+                }
+            }
+            Shapes::Polygon { points } => {
+                self.fill_shape(points, state, transform)
+            }
+            Shapes::Polyline { points } => self.draw_line(
+                points,
+                false,
+                state.line_width,
+                state.line_color,
+                state,
+                transform,
+            ),
+            Shapes::Custom {
+                mode,
+                vertices,
+                texture,
+            } => {
+                match texture {
+                    None => self.draw_custom(*mode, vertices, transform),
+                    Some(_) => warn_not_drawn(),
+                }
+                if *mode == ShapeMode::Polygon {
+                    let points: Vec<_> =
+                        vertices.iter().map(|v| v.pos).collect();
+                    let triangles = outline(&points, state.outline_thickness);
+                    self.fill_triangles(
+                        &triangles,
+                        state.outline_color,
+                        transform,
+                    );
+                }
+            }
+            Shapes::Clear { color } => self.clear(*color),
+            Shapes::Texture { .. } | Shapes::Text { .. } => warn_not_drawn(),
+        }
+    }
+
+    /// Fill the polygon `points` with the fill color and outline it with the outline color of `state`.
+    /// Like for SFML's shapes, the outline lies outside of the polygon.
+    fn fill_shape(
+        &mut self,
+        points: &[(f32, f32)],
+        state: &RenderParameterState,
+        transform: &Transform,
+    ) {
+        let triangles: Vec<_> =
+            triangulate(points).iter().map(|i| points[*i]).collect();
+        self.fill_triangles(&triangles, state.fill_color, transform);
+        let triangles = outline(points, state.outline_thickness);
+        self.fill_triangles(&triangles, state.outline_color, transform);
+    }
+
+    /// Draw a line through `points` of width `width` and color `color`,
+    /// with the line cap and join from `state`.
+    /// Lines with a width of up to `1` are drawn one pixel wide, like SFML's hairlines.
+    fn draw_line(
+        &mut self,
+        points: &[(f32, f32)],
+        closed: bool,
+        width: f32,
+        color: Color,
+        state: &RenderParameterState,
+        transform: &Transform,
+    ) {
+        if width <= 0. {
+            return;
+        }
+        if width <= 1. {
+            // The width of a hairline does not change with the transform.
+            let points: Vec<_> =
+                points.iter().map(|p| transform.apply(*p)).collect();
+            let triangles =
+                stroke(&points, closed, 1., LineCap::Butt, LineJoin::Bevel);
+            self.fill_triangles(&triangles, color, &Transform::IDENTITY);
+        } else {
+            let triangles =
+                stroke(points, closed, width, state.line_cap, state.line_join);
+            self.fill_triangles(&triangles, color, transform);
+        }
+    }
+
+    /// Draw the vertices of a shape from [`Window::begin_shape`] in mode `mode`.
+    ///
+    /// [`Window::begin_shape`]: crate::window::Window::begin_shape
+    fn draw_custom(
+        &mut self,
+        mode: ShapeMode,
+        vertices: &[ShapeVertex],
+        transform: &Transform,
+    ) {
+        let vertex = |v: &ShapeVertex| (transform.apply(v.pos), v.color);
+        match mode {
+            ShapeMode::Points => {
+                for v in vertices {
+                    let (x, y) = transform.apply(v.pos);
+                    self.blend_pixel(
+                        x.floor() as i64,
+                        y.floor() as i64,
+                        v.color,
+                    );
+                }
+            }
+            ShapeMode::Lines | ShapeMode::LineStrip => {
+                let step = if mode == ShapeMode::Lines { 2 } else { 1 };
+                for segment in vertices.windows(2).step_by(step) {
+                    let (a, b) = (
+                        transform.apply(segment[0].pos),
+                        transform.apply(segment[1].pos),
+                    );
+                    let triangles = stroke(
+                        &[a, b],
+                        false,
+                        1.,
+                        LineCap::Butt,
+                        LineJoin::Bevel,
+                    );
+                    // Blend the colors of the two vertices along the line, like SFML does
+                    let length = (b.0 - a.0).powi(2) + (b.1 - a.1).powi(2);
+                    let vertex = |p: (f32, f32)| {
+                        let t = ((p.0 - a.0) * (b.0 - a.0)
+                            + (p.1 - a.1) * (b.1 - a.1))
+                            / length;
+                        (p, mix(segment[0].color, segment[1].color, t))
+                    };
+                    for t in triangles.chunks_exact(3) {
+                        self.fill_triangle([
+                            vertex(t[0]),
+                            vertex(t[1]),
+                            vertex(t[2]),
+                        ]);
+                    }
+                }
+            }
+            ShapeMode::Triangles => {
+                for t in vertices.chunks_exact(3) {
+                    self.fill_triangle([
+                        vertex(&t[0]),
+                        vertex(&t[1]),
+                        vertex(&t[2]),
+                    ]);
+                }
+            }
+            ShapeMode::TriangleFan => {
+                for i in 2..vertices.len() {
+                    self.fill_triangle([
+                        vertex(&vertices[0]),
+                        vertex(&vertices[i - 1]),
+                        vertex(&vertices[i]),
+                    ]);
+                }
+            }
+            ShapeMode::TriangleStrip => {
+                for t in vertices.windows(3) {
+                    self.fill_triangle([
+                        vertex(&t[0]),
+                        vertex(&t[1]),
+                        vertex(&t[2]),
+                    ]);
+                }
+            }
+            ShapeMode::Polygon => {
+                let points: Vec<_> = vertices.iter().map(|v| v.pos).collect();
+                for t in triangulate(&points).chunks_exact(3) {
+                    self.fill_triangle([
+                        vertex(&vertices[t[0]]),
+                        vertex(&vertices[t[1]]),
+                        vertex(&vertices[t[2]]),
+                    ]);
+                }
+            }
+        }
+    }
+
+    /// Fill the triangles `triangles`, where every three consecutive vertices form one triangle,
+    /// in color `color`.
+    fn fill_triangles(
+        &mut self,
+        triangles: &[(f32, f32)],
+        color: Color,
+        transform: &Transform,
+    ) {
+        for t in triangles.chunks_exact(3) {
+            self.fill_triangle([
+                (transform.apply(t[0]), color),
+                (transform.apply(t[1]), color),
+                (transform.apply(t[2]), color),
+            ]);
+        }
+    }
+
+    /// Fill the triangle with the vertices `vertices`, blending their colors across the triangle.
+    /// Like on the graphics card, a pixel is filled if it's center lies inside the triangle,
+    /// with pixel centers on the top or left edge counting as inside,
+    /// so that triangles sharing an edge don't both fill the pixels along it.
+    fn fill_triangle(&mut self, mut vertices: [((f32, f32), Color); 3]) {
+        let edge = |a: (f32, f32), b: (f32, f32), p: (f32, f32)| {
+            (b.0 - a.0) * (p.1 - a.1) - (b.1 - a.1) * (p.0 - a.0)
+        };
+        let mut area = edge(vertices[0].0, vertices[1].0, vertices[2].0);
+        if area < 0. {
+            vertices.swap(1, 2);
+            area = -area;
+        }
+        if area == 0. || !area.is_finite() {
+            return;
+        }
+        let [(p0, c0), (p1, c1), (p2, c2)] = vertices;
+
+        // For the orientation of the triangle after the swap above,
+        // top edges point right and left edges point up.
+        let top_left = |a: (f32, f32), b: (f32, f32)| {
+            (a.1 == b.1 && b.0 > a.0) || b.1 < a.1
+        };
+        let edges = [(p1, p2), (p2, p0), (p0, p1)];
+        let inside = |w: f32, (a, b): ((f32, f32), (f32, f32))| {
+            w > 0. || (w == 0. && top_left(a, b))
+        };
+
+        let clamp = |v: f32, max: u32| v.clamp(0., max as f32) as u32;
+        let min_x = clamp(p0.0.min(p1.0).min(p2.0).floor(), self.width);
+        let max_x = clamp(p0.0.max(p1.0).max(p2.0).ceil(), self.width);
+        let min_y = clamp(p0.1.min(p1.1).min(p2.1).floor(), self.height);
+        let max_y = clamp(p0.1.max(p1.1).max(p2.1).ceil(), self.height);
+
+        let uniform = c0 == c1 && c1 == c2;
+        for y in min_y..max_y {
+            for x in min_x..max_x {
+                let p = (x as f32 + 0.5, y as f32 + 0.5);
+                let w = [edge(p1, p2, p), edge(p2, p0, p), edge(p0, p1, p)];
+                if !(0..3).all(|i| inside(w[i], edges[i])) {
+                    continue;
+                }
+                let color = if uniform {
+                    c0
+                } else {
+                    let mix = |f: fn(&Color) -> u8| {
+                        let v = (w[0] * f(&c0) as f32
+                            + w[1] * f(&c1) as f32
+                            + w[2] * f(&c2) as f32)
+                            / area;
+                        v.round().clamp(0., 255.) as u8
+                    };
+                    Color::rgba(
+                        mix(Color::red),
+                        mix(Color::green),
+                        mix(Color::blue),
+                        mix(Color::alpha),
+                    )
+                };
+                self.blend_pixel(x as i64, y as i64, color);
+            }
+        }
+    }
+
+    /// Draw `color` over the pixel at `(x, y)`, blending by it's alpha like SFML does by default.
+    fn blend_pixel(&mut self, x: i64, y: i64, color: Color) {
+        if x < 0 || y < 0 || x >= self.width as i64 || y >= self.height as i64 {
+            return;
+        }
+        let pixel =
+            &mut self.pixels[y as usize * self.width as usize + x as usize];
+        *pixel = match color.alpha() {
+            0 => return,
+            255 => color,
+            alpha => {
+                let a = alpha as f32 / 255.;
+                let mix = |src: u8, dst: u8| {
+                    (src as f32 * a + dst as f32 * (1. - a)).round() as u8
+                };
+                Color::rgba(
+                    mix(color.red(), pixel.red()),
+                    mix(color.green(), pixel.green()),
+                    mix(color.blue(), pixel.blue()),
+                    (alpha as f32 + pixel.alpha() as f32 * (1. - a)).round()
+                        as u8,
+                )
+            }
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pixel(raster: &Raster, x: u32, y: u32) -> Color {
+        raster.pixels()[(y * raster.width() + x) as usize]
+    }
+
+    fn count(raster: &Raster, color: Color) -> usize {
+        raster.pixels().iter().filter(|c| **c == color).count()
+    }
+
+    fn line_task(
+        from: (f32, f32),
+        to: (f32, f32),
+        transform: Transform,
+    ) -> RenderTask {
+        RenderTask {
+            pos: (0., 0.),
+            shape: Shapes::Lines {
+                coords: vec![from, to],
+            },
+            render_parameter_state: RenderParameterState {
+                line_color: Color::WHITE,
+                ..Default::default()
+            },
+            transform,
+        }
+    }
+
+    #[test]
+    fn top_left_fill_rule() {
+        let mut raster = Raster::new(4, 4);
+        let red = Color::RED;
+        // The diagonal passes through the centers of four pixels,
+        // which only belong to the triangle that has it as it's left edge.
+        raster.fill_triangles(
+            &[(0., 0.), (4., 0.), (0., 4.)],
+            red,
+            &Transform::IDENTITY,
+        );
+        assert_eq!(count(&raster, red), 6);
+        assert!(pixel(&raster, 0, 3) == Color::BLACK);
+        assert!(pixel(&raster, 2, 0) == red);
+        assert!(pixel(&raster, 3, 0) == Color::BLACK);
+
+        raster.fill_triangles(
+            &[(4., 0.), (4., 4.), (0., 4.)],
+            Color::BLUE,
+            &Transform::IDENTITY,
+        );
+        assert_eq!(count(&raster, red), 6);
+        assert_eq!(count(&raster, Color::BLUE), 10);
+    }
+
+    #[test]
+    fn shared_edges_are_not_blended_twice() {
+        let mut raster = Raster::new(8, 8);
+        let square =
+            [(0., 0.), (8., 0.), (8., 8.), (0., 0.), (8., 8.), (0., 8.)];
+        raster.fill_triangles(
+            &square,
+            Color::rgba(255, 255, 255, 128),
+            &Transform::IDENTITY,
+        );
+        let expected = Color::rgba(128, 128, 128, 255);
+        assert_eq!(count(&raster, expected), 64);
+    }
+
+    #[test]
+    fn vertex_colors_are_mixed() {
+        let mut raster = Raster::new(10, 1);
+        let (left, right) = ((0., 0.), (10., 0.));
+        let (red, blue) = (Color::RED, Color::BLUE);
+        // A gradient from red on the left to blue on the right
+        raster.fill_triangle([(left, red), (right, blue), ((0., 1.), red)]);
+        raster.fill_triangle([
+            (right, blue),
+            ((10., 1.), blue),
+            ((0., 1.), red),
+        ]);
+        for x in 0..10 {
+            let c = pixel(&raster, x, 0);
+            let t = (x as f32 + 0.5) / 10.;
+            assert!((c.red() as f32 - 255. * (1. - t)).abs() <= 1.);
+            assert!((c.blue() as f32 - 255. * t).abs() <= 1.);
+            assert_eq!(c.green(), 0);
+            assert_eq!(c.alpha(), 255);
+        }
+    }
+
+    #[test]
+    fn alpha_blending() {
+        let mut raster = Raster::new(2, 1);
+        raster.pixels[1] = Color::TRANSPARENT;
+        let white = Color::rgba(255, 255, 255, 128);
+        raster.blend_pixel(0, 0, white);
+        raster.blend_pixel(1, 0, white);
+        assert!(pixel(&raster, 0, 0) == Color::rgba(128, 128, 128, 255));
+        assert!(pixel(&raster, 1, 0) == Color::rgba(128, 128, 128, 128));
+
+        // Fully transparent colors and pixels outside of the raster change nothing
+        raster.blend_pixel(0, 0, Color::rgba(255, 0, 0, 0));
+        raster.blend_pixel(-1, 0, Color::WHITE);
+        raster.blend_pixel(2, 0, Color::WHITE);
+        assert!(pixel(&raster, 0, 0) == Color::rgba(128, 128, 128, 255));
+        assert!(pixel(&raster, 1, 0) == Color::rgba(128, 128, 128, 128));
+    }
+
+    #[test]
+    fn hairlines_are_one_pixel_wide() {
+        let mut raster = Raster::new(10, 10);
+        raster.draw_task(&line_task((1., 5.), (9., 5.), Transform::IDENTITY));
+        assert_eq!(count(&raster, Color::WHITE), 8);
+        assert!((1..9).all(|x| pixel(&raster, x, 4) == Color::WHITE));
+
+        // Even when scaled up
+        let mut raster = Raster::new(10, 20);
+        let scaled = Transform::IDENTITY.scale(3., 3.);
+        raster.draw_task(&line_task((1., 5.), (3., 5.), scaled));
+        assert_eq!(count(&raster, Color::WHITE), 6);
+        assert!((3..9).all(|x| pixel(&raster, x, 14) == Color::WHITE));
+    }
+
+    #[test]
+    fn custom_line_colors_are_mixed() {
+        let mut raster = Raster::new(10, 2);
+        let vertex = |x, color| ShapeVertex {
+            pos: (x, 1.),
+            color,
+            uv: (0., 0.),
+        };
+        raster.draw_custom(
+            ShapeMode::Lines,
+            &[vertex(0., Color::RED), vertex(10., Color::BLUE)],
+            &Transform::IDENTITY,
+        );
+        for x in 0..10 {
+            let c = pixel(&raster, x, 0);
+            let t = (x as f32 + 0.5) / 10.;
+            assert!((c.red() as f32 - 255. * (1. - t)).abs() <= 1.);
+            assert!((c.blue() as f32 - 255. * t).abs() <= 1.);
+        }
+        assert!(pixel(&raster, 0, 1) == Color::BLACK);
+    }
+
+    #[test]
+    fn wide_lines_scale() {
+        let mut raster = Raster::new(20, 20);
+        let mut task =
+            line_task((1., 5.), (5., 5.), Transform::IDENTITY.scale(2., 2.));
+        task.render_parameter_state.line_width = 2.;
+        raster.draw_task(&task);
+        // 8 by 4 pixels after scaling
+        assert_eq!(count(&raster, Color::WHITE), 32);
+    }
+
+    #[test]
+    fn draw_premultiplied_layer() {
+        let translucent = Color::rgba(255, 0, 0, 128);
+        let square =
+            [(0., 0.), (2., 0.), (2., 2.), (0., 0.), (2., 2.), (0., 2.)];
+
+        // Drawing onto a transparent layer, and the layer onto the frame,
+        // looks the same as drawing onto the frame directly.
+        let mut layer = Raster::new(2, 2);
+        layer.clear(Color::TRANSPARENT);
+        layer.fill_triangles(&square, translucent, &Transform::IDENTITY);
+        let mut frame = Raster::new(2, 2);
+        frame.clear(Color::WHITE);
+        frame.draw_raster(&layer);
+
+        let mut direct = Raster::new(2, 2);
+        direct.clear(Color::WHITE);
+        direct.fill_triangles(&square, translucent, &Transform::IDENTITY);
+        assert!(frame.pixels() == direct.pixels());
+        assert!(pixel(&frame, 0, 0) == Color::rgba(255, 127, 127, 255));
+
+        // A transparent layer leaves the frame as it is
+        layer.clear(Color::TRANSPARENT);
+        frame.clear(Color::GREEN);
+        frame.draw_raster(&layer);
+        assert_eq!(count(&frame, Color::GREEN), 4);
+    }
+}
@@ -1,12 +1,90 @@
-use crate::Color;
+use sfml::graphics::TextStyle;
 
-#[derive(Clone, Copy, PartialEq, Eq)]
+use crate::{
+    canvas::Canvas,
+    geometry::{LineCap, LineJoin},
+    shape::ArcMode,
+    text::{HorizontalAlign, TextOverflow, VerticalAlign},
+    Color,
+};
+
+/// How the position and size passed to drawing functions like [`Window::rectangle`] are interpreted.
+/// Set with [`Window::rect_mode`], [`Window::ellipse_mode`] and [`Window::image_mode`].
+///
+/// [`Window::rectangle`]: crate::window::Window::rectangle
+/// [`Window::rect_mode`]: crate::window::Window::rect_mode
+/// [`Window::ellipse_mode`]: crate::window::Window::ellipse_mode
+/// [`Window::image_mode`]: crate::window::Window::image_mode
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DrawMode {
+    /// The position is the top left corner of the shape, the size is it's width and height.
+    Corner,
+    /// The position is one corner of the shape, the size is the position of the opposite corner.
+    Corners,
+    /// The position is the center of the shape, the size is it's width and height.
+    Center,
+    /// The position is the center of the shape, the size is half of it's width and height,
+    /// i.e. for ellipses, it's radii.
+    Radius,
+}
+
+impl DrawMode {
+    /// The top left corner and the size of a rectangle drawn at `pos` with size `size`.
+    pub fn rectangle(
+        self,
+        pos: (f32, f32),
+        size: (f32, f32),
+    ) -> ((f32, f32), (f32, f32)) {
+        match self {
+            DrawMode::Corner => (pos, size),
+            DrawMode::Corners => (
+                (pos.0.min(size.0), pos.1.min(size.1)),
+                ((size.0 - pos.0).abs(), (size.1 - pos.1).abs()),
+            ),
+            DrawMode::Center => {
+                ((pos.0 - size.0 / 2., pos.1 - size.1 / 2.), size)
+            }
+            DrawMode::Radius => {
+                ((pos.0 - size.0, pos.1 - size.1), (2. * size.0, 2. * size.1))
+            }
+        }
+    }
+
+    /// The center and the radii of an ellipse drawn at `pos` with size `size`,
+    /// i.e. the ellipse filling the rectangle drawn at `pos` with size `size`.
+    pub fn ellipse(
+        self,
+        pos: (f32, f32),
+        size: (f32, f32),
+    ) -> ((f32, f32), (f32, f32)) {
+        let ((x, y), (w, h)) = self.rectangle(pos, size);
+        ((x + w / 2., y + h / 2.), (w / 2., h / 2.))
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
 pub struct RenderParameterState {
     pub fill_color: Color,
     pub outline_color: Color,
+    pub outline_thickness: f32,
     pub line_color: Color,
+    pub line_width: f32,
+    pub line_cap: LineCap,
+    pub line_join: LineJoin,
+    pub arc_mode: ArcMode,
+    pub rect_mode: DrawMode,
+    pub ellipse_mode: DrawMode,
+    pub image_mode: DrawMode,
+    pub tint: Color,
+    pub texture_offset: (f32, f32),
+    pub texture_scale: (f32, f32),
     pub font_color: Color,
     pub font_size: u32,
+    pub text_style: TextStyle,
+    pub text_align: (HorizontalAlign, VerticalAlign),
+    pub line_spacing: f32,
+    pub text_overflow: TextOverflow,
+    pub target: Option<Canvas>,
 }
 
 impl Default for RenderParameterState {
@@ -14,9 +92,25 @@ impl Default for RenderParameterState {
         Self {
             fill_color: Color::BLACK,
             outline_color: Color::TRANSPARENT,
+            outline_thickness: 1.,
             line_color: Color::BLACK,
+            line_width: 1.,
+            line_cap: LineCap::Butt,
+            line_join: LineJoin::Miter,
+            arc_mode: ArcMode::Pie,
+            rect_mode: DrawMode::Corner,
+            ellipse_mode: DrawMode::Radius,
+            image_mode: DrawMode::Corner,
+            tint: Color::WHITE,
+            texture_offset: (0., 0.),
+            texture_scale: (1., 1.),
             font_color: Color::BLACK,
             font_size: 16,
+            text_style: TextStyle::REGULAR,
+            text_align: (HorizontalAlign::Left, VerticalAlign::Top),
+            line_spacing: 1.,
+            text_overflow: TextOverflow::Clip,
+            target: None,
         }
     }
 }
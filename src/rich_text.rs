@@ -0,0 +1,563 @@
+use sfml::graphics::TextStyle;
+
+use crate::{
+    assets::Assets,
+    text::{
+        ascent, descent, line_spacing, strikethrough, text_width, TextFormat,
+    },
+    Color, Font, HorizontalAlign, VerticalAlign,
+};
+
+/// A piece of text inside a [`RichText`], with it's own color, size, font and style.
+/// Since SFML's [`TextStyle`] has no strikethrough, that is a separate flag.
+/// Any of color, size and font that are not set are taken from the
+/// current settings of the [`Window`] the text is drawn to.
+///
+/// [`Window`]: crate::window::Window
+#[derive(Clone, PartialEq)]
+pub struct Span {
+    pub text: String,
+    pub color: Option<Color>,
+    pub size: Option<u32>,
+    pub font: Option<Font>,
+    pub style: TextStyle,
+    pub strikethrough: bool,
+}
+
+impl Span {
+    fn new(text: &str) -> Self {
+        Self {
+            text: text.to_string(),
+            color: None,
+            size: None,
+            font: None,
+            style: TextStyle::REGULAR,
+            strikethrough: false,
+        }
+    }
+}
+
+/// Text made up of [`Span`]s with different colors, sizes, fonts and styles,
+/// which can be drawn with [`Window::rich_text`].
+///
+/// A [`RichText`] can either be built up span by span, where the style functions
+/// like [`RichText::color`] apply to the last span added with [`RichText::span`],
+/// or be parsed from markup with [`RichText::parse`].
+///
+/// # Examples
+/// ```
+/// let text = RichText::new()
+///     .span("Hello ")
+///     .span("world")
+///     .color(Color::RED)
+///     .bold()
+///     .span("!");
+/// // Or equivalently
+/// let text = RichText::parse("Hello [color=red][b]world[/b][/color]!");
+/// ```
+///
+/// [`Window::rich_text`]: crate::window::Window::rich_text
+#[derive(Clone, PartialEq, Default)]
+pub struct RichText {
+    spans: Vec<Span>,
+}
+
+impl RichText {
+    /// Create an empty [`RichText`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a new span with text `text`, drawn with the window's current text settings
+    /// until changed with functions like [`RichText::color`].
+    pub fn span(mut self, text: &str) -> Self {
+        self.spans.push(Span::new(text));
+        self
+    }
+
+    fn with_last(mut self, f: impl FnOnce(&mut Span)) -> Self {
+        if let Some(span) = self.spans.last_mut() {
+            f(span);
+        }
+        self
+    }
+
+    /// Set the color of the last span.
+    pub fn color<C: Into<Color>>(self, color: C) -> Self {
+        let color = color.into();
+        self.with_last(|s| s.color = Some(color))
+    }
+
+    /// Set the font size of the last span.
+    pub fn size(self, size: u32) -> Self {
+        self.with_last(|s| s.size = Some(size))
+    }
+
+    /// Set the font of the last span.
+    pub fn font(self, font: Font) -> Self {
+        self.with_last(|s| s.font = Some(font))
+    }
+
+    /// Add the style `style` (like [`TextStyle::BOLD`]) to the last span.
+    pub fn style(self, style: TextStyle) -> Self {
+        self.with_last(|s| s.style |= style)
+    }
+
+    /// Make the last span bold.
+    pub fn bold(self) -> Self {
+        self.style(TextStyle::BOLD)
+    }
+
+    /// Make the last span italic.
+    pub fn italic(self) -> Self {
+        self.style(TextStyle::ITALIC)
+    }
+
+    /// Underline the last span.
+    pub fn underlined(self) -> Self {
+        self.style(TextStyle::UNDERLINED)
+    }
+
+    /// Strike through the last span.
+    pub fn strikethrough(self) -> Self {
+        self.with_last(|s| s.strikethrough = true)
+    }
+
+    /// The spans making up this text.
+    pub fn spans(&self) -> &[Span] {
+        &self.spans
+    }
+
+    /// Parse markup into a [`RichText`].
+    /// The following tags are supported, and can be nested:
+    ///
+    /// - `[color=...]...[/color]`, with a color name like `red` or `light_gray`,
+    ///   or a hex color like `#1D3785` or `#1D378580`.
+    /// - `[size=...]...[/size]`, with a font size like `24`.
+    /// - `[b]...[/b]` for bold text.
+    /// - `[i]...[/i]` for italic text.
+    /// - `[u]...[/u]` for underlined text.
+    /// - `[s]...[/s]` for struck through text.
+    ///
+    /// A literal `[` can be written as `[[`.
+    /// Anything else in square brackets that is not one of these tags is kept as it is.
+    ///
+    /// # Examples
+    /// ```
+    /// let text = RichText::parse("[b]Error:[/b] [color=#FF4444]file not found[/color]");
+    /// ```
+    pub fn parse(markup: &str) -> Self {
+        enum Tag {
+            Color(Color),
+            Size(u32),
+            Style(TextStyle),
+            Strikethrough,
+        }
+
+        fn parse_tag(tag: &str) -> Option<(&str, Option<Tag>)> {
+            if let Some(name) = tag.strip_prefix('/') {
+                return match name {
+                    "color" | "size" | "b" | "i" | "u" | "s" => {
+                        Some((name, None))
+                    }
+                    _ => None,
+                };
+            }
+            let (name, value) = match tag.split_once('=') {
+                Some((name, value)) => (name, Some(value)),
+                None => (tag, None),
+            };
+            let tag = match (name, value) {
+                ("color", Some(value)) => Tag::Color(parse_color(value)?),
+                ("size", Some(value)) => Tag::Size(value.parse().ok()?),
+                ("b", None) => Tag::Style(TextStyle::BOLD),
+                ("i", None) => Tag::Style(TextStyle::ITALIC),
+                ("u", None) => Tag::Style(TextStyle::UNDERLINED),
+                ("s", None) => Tag::Strikethrough,
+                _ => return None,
+            };
+            Some((name, Some(tag)))
+        }
+
+        let mut rich_text = RichText::new();
+        let mut stack: Vec<(&str, Tag)> = Vec::new();
+        let mut text = String::new();
+
+        let flush = |rich_text: &mut RichText,
+                     text: &mut String,
+                     stack: &[(&str, Tag)]| {
+            if text.is_empty() {
+                return;
+            }
+            let mut span = Span::new(text);
+            for (_, tag) in stack {
+                match tag {
+                    Tag::Color(c) => span.color = Some(*c),
+                    Tag::Size(s) => span.size = Some(*s),
+                    Tag::Style(s) => span.style |= *s,
+                    Tag::Strikethrough => span.strikethrough = true,
+                }
+            }
+            rich_text.spans.push(span);
+            text.clear();
+        };
+
+        let mut rest = markup;
+        while let Some(i) = rest.find('[') {
+            text.push_str(&rest[..i]);
+            rest = &rest[i..];
+
+            if let Some(after) = rest.strip_prefix("[[") {
+                text.push('[');
+                rest = after;
+                continue;
+            }
+
+            let tag = rest[1..]
+                .find(']')
+                .and_then(|end| Some((end, parse_tag(&rest[1..end + 1])?)));
+            match tag {
+                Some((end, (name, tag))) => {
+                    flush(&mut rich_text, &mut text, &stack);
+                    match tag {
+                        Some(tag) => stack.push((name, tag)),
+                        None => {
+                            if let Some(i) =
+                                stack.iter().rposition(|(n, _)| *n == name)
+                            {
+                                stack.remove(i);
+                            }
+                        }
+                    }
+                    rest = &rest[end + 2..];
+                }
+                None => {
+                    text.push('[');
+                    rest = &rest[1..];
+                }
+            }
+        }
+        text.push_str(rest);
+        flush(&mut rich_text, &mut text, &stack);
+
+        rich_text
+    }
+}
+
+/// Parse a color name like `red`, or a hex color like `#1D3785` or `#1D378580`.
+fn parse_color(value: &str) -> Option<Color> {
+    if let Some(hex) = value.strip_prefix('#') {
+        let channel = |i: usize| {
+            hex.get(2 * i..2 * i + 2)
+                .and_then(|c| u8::from_str_radix(c, 16).ok())
+        };
+        return match hex.len() {
+            6 => Some(Color::rgb(channel(0)?, channel(1)?, channel(2)?)),
+            8 => Some(Color::rgba(
+                channel(0)?,
+                channel(1)?,
+                channel(2)?,
+                channel(3)?,
+            )),
+            _ => None,
+        };
+    }
+    Some(match value.to_lowercase().as_str() {
+        "transparent" => Color::TRANSPARENT,
+        "black" => Color::BLACK,
+        "white" => Color::WHITE,
+        "gray" | "grey" => Color::GRAY,
+        "dark_gray" | "dark_grey" => Color::DARK_GRAY,
+        "light_gray" | "light_grey" => Color::LIGHT_GRAY,
+        "red" => Color::RED,
+        "green" => Color::GREEN,
+        "blue" => Color::BLUE,
+        _ => return None,
+    })
+}
+
+/// A piece of a [`RichText`] laid out by [`layout`], to be drawn as a single SFML text.
+pub struct Fragment {
+    pub string: String,
+    /// The top left of the fragment as drawn by SFML, relative to the position of the text.
+    pub offset: (f32, f32),
+    pub color: Color,
+    pub size: u32,
+    pub font: Option<Font>,
+    pub style: TextStyle,
+    /// The line striking through the fragment, if any, as `(left, top, width, height)`
+    /// relative to the position of the text.
+    pub strikethrough: Option<(f32, f32, f32, f32)>,
+}
+
+/// Lay out `rich_text` line by line, using the fonts from `assets`, with the settings `color`, `size` and `font`
+/// for anything not set by the spans, and aligned according to `align`.
+pub fn layout(
+    assets: &Assets,
+    rich_text: &RichText,
+    color: Color,
+    size: u32,
+    font: Option<Font>,
+    (horizontal, vertical): (HorizontalAlign, VerticalAlign),
+) -> Vec<Fragment> {
+    // Split the spans into lines of fragments, with their offsets only along the line for now.
+    let mut lines: Vec<Vec<Fragment>> = vec![Vec::new()];
+    for span in &rich_text.spans {
+        for (i, piece) in span.text.split('\n').enumerate() {
+            if i > 0 {
+                lines.push(Vec::new());
+            }
+            if !piece.is_empty() {
+                lines.last_mut().unwrap().push(Fragment {
+                    string: piece.to_string(),
+                    offset: (0., 0.),
+                    color: span.color.unwrap_or(color),
+                    size: span.size.unwrap_or(size),
+                    font: span.font.or(font),
+                    style: span.style,
+                    // Placed below, once the fragment's position is known.
+                    strikethrough: span
+                        .strikethrough
+                        .then_some((0., 0., 0., 0.)),
+                });
+            }
+        }
+    }
+
+    let format = |f: &Fragment| {
+        assets.font_or_default(f.font).map(|font| TextFormat {
+            font,
+            size: f.size,
+            style: f.style,
+        })
+    };
+    let base_format = assets.font_or_default(font).map(|font| TextFormat {
+        font,
+        size,
+        style: TextStyle::REGULAR,
+    });
+    // The maximum of `metric` over all fragments of a line,
+    // or the metric of the default format for empty lines.
+    let line_max = |line: &[Fragment], metric: &dyn Fn(&TextFormat) -> f32| {
+        let formats: Vec<_> = if line.is_empty() {
+            base_format.into_iter().collect()
+        } else {
+            line.iter().filter_map(format).collect()
+        };
+        formats.iter().map(metric).fold(0., f32::max)
+    };
+
+    // Place the lines' baselines below each other, starting at zero.
+    let mut baselines = Vec::with_capacity(lines.len());
+    let mut baseline = 0.;
+    for (i, line) in lines.iter().enumerate() {
+        if i > 0 {
+            baseline += line_max(line, &line_spacing);
+        }
+        baselines.push(baseline);
+    }
+
+    let first = &lines[0];
+    let top = -line_max(first, &ascent);
+    let bottom = baseline + line_max(lines.last().unwrap(), &descent);
+    let shift = match vertical {
+        // Like for plain text, with the baseline at `size` below the position.
+        VerticalAlign::Top => line_max(first, &|f| f.size as f32),
+        VerticalAlign::Baseline => 0.,
+        VerticalAlign::Center => -(top + bottom) / 2.,
+        VerticalAlign::Bottom => -bottom,
+    };
+
+    let mut fragments = Vec::new();
+    for (mut line, baseline) in lines.into_iter().zip(baselines) {
+        let mut x = 0.;
+        for fragment in &mut line {
+            let fragment_format = format(fragment);
+            let width = fragment_format
+                .map(|f| text_width(&f, &fragment.string))
+                .unwrap_or(0.);
+            // SFML puts the baseline at `size` below the top of the text.
+            fragment.offset = (x, baseline + shift - fragment.size as f32);
+            fragment.strikethrough =
+                fragment.strikethrough.and(fragment_format).map(|f| {
+                    let (y, thickness) = strikethrough(&f);
+                    let y = baseline + shift + y - thickness / 2.;
+                    (x, y, width, thickness)
+                });
+            x += width;
+        }
+        let x_shift = match horizontal {
+            HorizontalAlign::Left => 0.,
+            HorizontalAlign::Center => -x / 2.,
+            HorizontalAlign::Right => -x,
+        };
+        for fragment in &mut line {
+            fragment.offset.0 += x_shift;
+            if let Some(line) = &mut fragment.strikethrough {
+                line.0 += x_shift;
+            }
+        }
+        fragments.extend(line);
+    }
+    fragments
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The text and style of each span, which is enough to tell most parses apart.
+    fn summary(rich_text: &RichText) -> Vec<(&str, TextStyle, bool)> {
+        rich_text
+            .spans()
+            .iter()
+            .map(|s| (s.text.as_str(), s.style, s.strikethrough))
+            .collect()
+    }
+
+    #[test]
+    fn plain_text_is_one_span() {
+        let text = RichText::parse("Hello world");
+        assert_eq!(
+            summary(&text),
+            [("Hello world", TextStyle::REGULAR, false)]
+        );
+        assert!(text.spans()[0].color.is_none());
+        assert!(text.spans()[0].size.is_none());
+    }
+
+    #[test]
+    fn empty_markup_has_no_spans() {
+        assert!(RichText::parse("").spans().is_empty());
+        assert!(RichText::parse("[b][/b]").spans().is_empty());
+    }
+
+    #[test]
+    fn nested_tags_combine() {
+        let text = RichText::parse("a[b]b[i]c[/i]d[/b]e");
+        assert_eq!(
+            summary(&text),
+            [
+                ("a", TextStyle::REGULAR, false),
+                ("b", TextStyle::BOLD, false),
+                ("c", TextStyle::BOLD | TextStyle::ITALIC, false),
+                ("d", TextStyle::BOLD, false),
+                ("e", TextStyle::REGULAR, false),
+            ]
+        );
+    }
+
+    #[test]
+    fn overlapping_tags_close_by_name() {
+        let text = RichText::parse("[b]a[i]b[/b]c[/i]d");
+        assert_eq!(
+            summary(&text),
+            [
+                ("a", TextStyle::BOLD, false),
+                ("b", TextStyle::BOLD | TextStyle::ITALIC, false),
+                ("c", TextStyle::ITALIC, false),
+                ("d", TextStyle::REGULAR, false),
+            ]
+        );
+    }
+
+    #[test]
+    fn unclosed_tags_last_until_the_end() {
+        let text = RichText::parse("a[u]b[s]c");
+        assert_eq!(
+            summary(&text),
+            [
+                ("a", TextStyle::REGULAR, false),
+                ("b", TextStyle::UNDERLINED, false),
+                ("c", TextStyle::UNDERLINED, true),
+            ]
+        );
+    }
+
+    #[test]
+    fn unopened_closing_tags_are_dropped() {
+        let text = RichText::parse("a[/b]b");
+        assert_eq!(
+            summary(&text),
+            [
+                ("a", TextStyle::REGULAR, false),
+                ("b", TextStyle::REGULAR, false)
+            ]
+        );
+    }
+
+    #[test]
+    fn unknown_and_malformed_tags_are_kept_as_text() {
+        for markup in [
+            "[foo]x",
+            "[color=nope]x",
+            "[size=big]x",
+            "[b=1]x",
+            "[/foo]x",
+            "[b x",
+            "a]b",
+        ] {
+            let text = RichText::parse(markup);
+            assert_eq!(
+                summary(&text),
+                [(markup, TextStyle::REGULAR, false)],
+                "{}",
+                markup
+            );
+        }
+    }
+
+    #[test]
+    fn double_brackets_escape() {
+        let text = RichText::parse("[[b]x");
+        assert_eq!(summary(&text), [("[b]x", TextStyle::REGULAR, false)]);
+    }
+
+    #[test]
+    fn color_and_size_tags() {
+        let text = RichText::parse("[size=24][color=#1D3785]a[/color]b[/size]");
+        let spans = text.spans();
+        assert_eq!(spans.len(), 2);
+        assert!(spans[0].color == Some(Color::rgb(0x1D, 0x37, 0x85)));
+        assert_eq!(spans[0].size, Some(24));
+        assert!(spans[1].color.is_none());
+        assert_eq!(spans[1].size, Some(24));
+    }
+
+    #[test]
+    fn inner_color_wins() {
+        let text = RichText::parse("[color=red][color=blue]a[/color]b[/color]");
+        let spans = text.spans();
+        assert!(spans[0].color == Some(Color::BLUE));
+        assert!(spans[1].color == Some(Color::RED));
+    }
+
+    #[test]
+    fn parse_color_names_and_hex() {
+        assert!(parse_color("red") == Some(Color::RED));
+        assert!(parse_color("Light_Grey") == Some(Color::LIGHT_GRAY));
+        assert!(parse_color("#ff000080") == Some(Color::rgba(255, 0, 0, 128)));
+        assert!(parse_color("#1D3785") == Some(Color::rgb(0x1D, 0x37, 0x85)));
+        assert!(parse_color("#123").is_none());
+        assert!(parse_color("#GG0000").is_none());
+        assert!(parse_color("#ÄÄÄ").is_none());
+        assert!(parse_color("purple").is_none());
+    }
+
+    #[test]
+    fn builder_styles_the_last_span() {
+        let text = RichText::new()
+            .bold()
+            .span("a")
+            .span("b")
+            .italic()
+            .strikethrough();
+        assert_eq!(
+            summary(&text),
+            [
+                ("a", TextStyle::REGULAR, false),
+                ("b", TextStyle::ITALIC, true)
+            ]
+        );
+    }
+}
@@ -1,6 +1,51 @@
-use sfml::graphics::{CircleShape, RectangleShape, Text};
+use sfml::graphics::{CircleShape, RectangleShape};
 
-use crate::{render_parameters::RenderParameterState, Font, Texture};
+use crate::{
+    render_parameters::RenderParameterState, transform::Transform, Color, Font,
+    Texture,
+};
+
+/// How the vertices of a shape built with [`Window::begin_shape`] are connected.
+///
+/// [`Window::begin_shape`]: crate::window::Window::begin_shape
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ShapeMode {
+    /// Each vertex is drawn as a single point.
+    Points,
+    /// Every two consecutive vertices form a separate hairline.
+    Lines,
+    /// All vertices are connected by one continuous hairline.
+    LineStrip,
+    /// Every three consecutive vertices form a separate triangle.
+    Triangles,
+    /// The first vertex is shared by all triangles, every further vertex forms a triangle with the previous one.
+    TriangleFan,
+    /// Every vertex forms a triangle with the two vertices before it.
+    TriangleStrip,
+    /// All vertices form the outline of one closed polygon, which may be concave.
+    Polygon,
+}
+
+/// Which parts of an arc drawn with [`Window::arc`] are filled and outlined.
+///
+/// [`Window::arc`]: crate::window::Window::arc
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ArcMode {
+    /// The area between the arc and the straight line connecting it's ends is filled,
+    /// and only the curved part is outlined.
+    Open,
+    /// The area between the arc and the straight line connecting it's ends is filled and outlined.
+    Chord,
+    /// The arc is drawn like a slice of pie, i.e. it is connected to it's center.
+    Pie,
+}
+
+#[derive(Clone, Copy)]
+pub struct ShapeVertex {
+    pub pos: (f32, f32),
+    pub color: Color,
+    pub uv: (f32, f32),
+}
 
 pub enum Shapes {
     Circle {
@@ -10,8 +55,25 @@ pub enum Shapes {
         width: f32,
         height: f32,
     },
+    Ellipse {
+        radius_x: f32,
+        radius_y: f32,
+    },
+    Arc {
+        radius: f32,
+        start: f32,
+        end: f32,
+    },
+    RoundedRectangle {
+        width: f32,
+        height: f32,
+        corner_radius: f32,
+    },
     Texture {
         texture: Texture,
+        /// The part of the texture to draw as `(left, top, width, height)` in pixels.
+        /// A negative width or height flips the texture.
+        region: (i32, i32, i32, i32),
         width: f32,
         height: f32,
     },
@@ -22,17 +84,31 @@ pub enum Shapes {
     Lines {
         coords: Vec<(f32, f32)>,
     },
+    Polygon {
+        points: Vec<(f32, f32)>,
+    },
+    Polyline {
+        points: Vec<(f32, f32)>,
+    },
+    Custom {
+        mode: ShapeMode,
+        vertices: Vec<ShapeVertex>,
+        texture: Option<Texture>,
+    },
+    /// Clear the whole target to a color.
+    Clear {
+        color: Color,
+    },
 }
 
 pub struct RenderTask {
     pub pos: (f32, f32),
     pub shape: Shapes,
     pub render_parameter_state: RenderParameterState,
+    pub transform: Transform,
 }
 
-pub struct ShapeStore<'a> {
-    pub circle: CircleShape<'a>,
-    pub rectangle: RectangleShape<'a>,
-    pub texture: RectangleShape<'a>,
-    pub text: Option<Text<'a>>,
+pub struct ShapeStore {
+    pub circle: CircleShape<'static>,
+    pub rectangle: RectangleShape<'static>,
 }
@@ -0,0 +1,172 @@
+use crate::texture::Texture;
+
+/// A part of a [`Texture`], like a frame of a [`SpriteSheet`],
+/// that can be passed to [`Window::sprite`] to draw it to the screen.
+///
+/// [`Window::sprite`]: crate::window::Window::sprite
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Sprite {
+    pub texture: Texture,
+    /// The part of the texture as `(left, top, width, height)` in pixels.
+    /// A negative width or height means that the sprite is flipped.
+    pub region: (i32, i32, i32, i32),
+}
+
+impl Sprite {
+    /// A sprite showing the whole texture `texture`.
+    pub fn new(texture: Texture) -> Self {
+        Self {
+            texture,
+            region: texture.region(),
+        }
+    }
+
+    /// The sprite mirrored horizontally, i.e. with left and right swapped.
+    pub fn flip_horizontal(self) -> Self {
+        let (left, top, width, height) = self.region;
+        Self {
+            region: (left + width, top, -width, height),
+            ..self
+        }
+    }
+
+    /// The sprite mirrored vertically, i.e. with top and bottom swapped.
+    pub fn flip_vertical(self) -> Self {
+        let (left, top, width, height) = self.region;
+        Self {
+            region: (left, top + height, width, -height),
+            ..self
+        }
+    }
+
+    /// The width of the sprite in pixels.
+    pub fn width(&self) -> u32 {
+        self.region.2.unsigned_abs()
+    }
+
+    /// The height of the sprite in pixels.
+    pub fn height(&self) -> u32 {
+        self.region.3.unsigned_abs()
+    }
+
+    /// The aspect ratio of the sprite.
+    /// (`.width()/.height()`)
+    pub fn aspect(&self) -> f32 {
+        let h = self.height();
+        if h > 0 {
+            (self.width() as f32) / (h as f32)
+        } else {
+            0.
+        }
+    }
+}
+
+/// A texture split into a grid of equally sized frames, like the frames of an animation
+/// or the tiles of a tile set.
+/// Frames are numbered row by row, starting at the top left.
+///
+/// # Examples
+/// ```
+/// let mut pg = Window::new_fullscreen();
+/// // A sheet with 8 frames per row and 4 rows
+/// let sheet = SpriteSheet::new(pg.load_texture("player.png").unwrap(), 8, 4);
+/// loop {
+///     let frame = (pg.time() * 10.) as usize % sheet.len();
+///     pg.sprite((100., 250.), sheet.frame(frame), 64., 64.);
+///
+///     pg.update();
+/// }
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct SpriteSheet {
+    texture: Texture,
+    columns: u32,
+    rows: u32,
+    frame_width: u32,
+    frame_height: u32,
+}
+
+impl SpriteSheet {
+    /// Split the texture `texture` into `columns` frames per row and `rows` rows.
+    /// The size of the frames is taken from the size of the texture at the time the sheet is created.
+    pub fn new(texture: Texture, columns: u32, rows: u32) -> Self {
+        let (columns, rows) = (columns.max(1), rows.max(1));
+        Self {
+            texture,
+            columns,
+            rows,
+            frame_width: texture.width() / columns,
+            frame_height: texture.height() / rows,
+        }
+    }
+
+    /// Split the texture `texture` into frames of `frame_width` by `frame_height` pixels.
+    /// Any leftover pixels at the right and bottom of the texture are ignored.
+    pub fn with_frame_size(
+        texture: Texture,
+        frame_width: u32,
+        frame_height: u32,
+    ) -> Self {
+        let (frame_width, frame_height) =
+            (frame_width.max(1), frame_height.max(1));
+        Self {
+            texture,
+            columns: texture.width() / frame_width,
+            rows: texture.height() / frame_height,
+            frame_width,
+            frame_height,
+        }
+    }
+
+    /// The texture the frames are taken from.
+    pub fn texture(&self) -> Texture {
+        self.texture
+    }
+
+    /// The number of frames per row.
+    pub fn columns(&self) -> u32 {
+        self.columns
+    }
+
+    /// The number of rows.
+    pub fn rows(&self) -> u32 {
+        self.rows
+    }
+
+    /// The number of frames.
+    pub fn len(&self) -> usize {
+        (self.columns * self.rows) as usize
+    }
+
+    /// Whether the sheet has no frames, which can only happen for
+    /// [`SpriteSheet::with_frame_size`] with frames larger than the texture.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The width of a frame in pixels.
+    pub fn frame_width(&self) -> u32 {
+        self.frame_width
+    }
+
+    /// The height of a frame in pixels.
+    pub fn frame_height(&self) -> u32 {
+        self.frame_height
+    }
+
+    /// The frame with number `index`.
+    /// Numbers past the last frame wrap around to the first frame.
+    pub fn frame(&self, index: usize) -> Sprite {
+        let index = index.checked_rem(self.len()).unwrap_or(0) as u32;
+        self.frame_at(index % self.columns.max(1), index / self.columns.max(1))
+    }
+
+    /// The frame in column `column` and row `row`, counting from zero.
+    pub fn frame_at(&self, column: u32, row: u32) -> Sprite {
+        let (w, h) = (self.frame_width(), self.frame_height());
+        Sprite {
+            texture: self.texture,
+            region: ((column * w) as i32, (row * h) as i32, w as i32, h as i32),
+        }
+    }
+}
@@ -0,0 +1,134 @@
+use std::sync::atomic::{AtomicU32, Ordering};
+
+/// The next generation handed out by any [`Store`].
+/// Generations are unique across all stores, so that handles from the assets of dropped windows
+/// are not mistaken for resources of a later store either.
+static NEXT_GENERATION: AtomicU32 = AtomicU32::new(0);
+
+/// A list of resources, like textures or fonts, that can be added and removed,
+/// and are referred to by an index together with a generation.
+/// Every added resource gets a new generation,
+/// so that handles to a removed resource are not mistaken for a later resource in the same slot.
+pub struct Store<T> {
+    slots: Vec<(u32, Option<T>)>,
+    free: Vec<usize>,
+}
+
+impl<T> Store<T> {
+    pub fn new() -> Self {
+        Self {
+            slots: Vec::new(),
+            free: Vec::new(),
+        }
+    }
+
+    pub fn get(&self, index: usize, generation: u32) -> Option<&T> {
+        match self.slots.get(index) {
+            Some((g, value)) if *g == generation => value.as_ref(),
+            _ => None,
+        }
+    }
+
+    pub fn get_mut(&mut self, index: usize, generation: u32) -> Option<&mut T> {
+        match self.slots.get_mut(index) {
+            Some((g, value)) if *g == generation => value.as_mut(),
+            _ => None,
+        }
+    }
+
+    /// Add `value` to the store, reusing the slot of a removed resource if possible.
+    /// Returns the index and generation of the slot.
+    pub fn add(&mut self, value: T) -> (usize, u32) {
+        let generation = NEXT_GENERATION.fetch_add(1, Ordering::Relaxed);
+        match self.free.pop() {
+            Some(index) => {
+                self.slots[index] = (generation, Some(value));
+                (index, generation)
+            }
+            None => {
+                self.slots.push((generation, Some(value)));
+                (self.slots.len() - 1, generation)
+            }
+        }
+    }
+
+    /// Remove the resource at `index` with generation `generation`, if it still exists.
+    pub fn remove(&mut self, index: usize, generation: u32) -> Option<T> {
+        match self.slots.get_mut(index) {
+            Some((g, value)) if *g == generation && value.is_some() => {
+                self.free.push(index);
+                value.take()
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn add_and_get() {
+        let mut store = Store::new();
+        let (a, ga) = store.add("a");
+        let (b, gb) = store.add("b");
+        assert_ne!(a, b);
+        assert_eq!(store.get(a, ga), Some(&"a"));
+        assert_eq!(store.get(b, gb), Some(&"b"));
+        assert_eq!(store.get(b + 1, gb), None);
+    }
+
+    #[test]
+    fn get_mut_changes_the_value() {
+        let mut store = Store::new();
+        let (i, g) = store.add(1);
+        *store.get_mut(i, g).unwrap() += 1;
+        assert_eq!(store.get(i, g), Some(&2));
+    }
+
+    #[test]
+    fn remove() {
+        let mut store = Store::new();
+        let (i, g) = store.add("a");
+        assert_eq!(store.remove(i, g), Some("a"));
+        assert_eq!(store.get(i, g), None);
+        assert_eq!(store.get_mut(i, g), None);
+        // Removing twice does nothing
+        assert_eq!(store.remove(i, g), None);
+    }
+
+    #[test]
+    fn reused_slot_rejects_stale_handle() {
+        let mut store = Store::new();
+        let (i, g) = store.add("old");
+        store.remove(i, g);
+        let (j, h) = store.add("new");
+        assert_eq!(i, j);
+        assert_ne!(g, h);
+        assert_eq!(store.get(i, g), None);
+        assert_eq!(store.remove(i, g), None);
+        assert_eq!(store.get(j, h), Some(&"new"));
+    }
+
+    #[test]
+    fn new_store_rejects_handles_of_old_store() {
+        let mut old = Store::new();
+        let (i, g) = old.add("old");
+        drop(old);
+        let mut store = Store::new();
+        let (j, h) = store.add("new");
+        assert_eq!(i, j);
+        assert_eq!(store.get(i, g), None);
+        assert_eq!(store.get(j, h), Some(&"new"));
+    }
+
+    #[test]
+    fn wrong_generation_is_rejected() {
+        let mut store = Store::new();
+        let (i, g) = store.add("a");
+        assert_eq!(store.get(i, g.wrapping_add(1)), None);
+        assert_eq!(store.remove(i, g.wrapping_add(1)), None);
+        assert_eq!(store.get(i, g), Some(&"a"));
+    }
+}
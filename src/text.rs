@@ -0,0 +1,289 @@
+use sfml::graphics::{Font as SfmlFont, Text, TextStyle};
+
+/// The font, size and style with which some text is drawn.
+#[derive(Clone, Copy)]
+pub struct TextFormat<'a> {
+    pub font: &'a SfmlFont,
+    pub size: u32,
+    pub style: TextStyle,
+}
+
+impl TextFormat<'_> {
+    fn bold(&self) -> bool {
+        self.style.contains(TextStyle::BOLD)
+    }
+
+    /// The SFML text object for `string` in this format.
+    pub fn text(&self, string: &str) -> Text<'_> {
+        let mut text = Text::new(string, self.font, self.size);
+        text.set_style(self.style);
+        text
+    }
+}
+
+/// How text drawn with [`Window::text`] is aligned horizontally relative to it's position.
+/// Set with [`Window::text_align`].
+///
+/// [`Window::text`]: crate::window::Window::text
+/// [`Window::text_align`]: crate::window::Window::text_align
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum HorizontalAlign {
+    /// The text starts at it's position.
+    Left,
+    /// The text is centered on it's position.
+    Center,
+    /// The text ends at it's position.
+    Right,
+}
+
+/// How text drawn with [`Window::text`] is aligned vertically relative to it's position.
+/// Set with [`Window::text_align`].
+///
+/// [`Window::text`]: crate::window::Window::text
+/// [`Window::text_align`]: crate::window::Window::text_align
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum VerticalAlign {
+    /// The position is at the top of the first line of text,
+    /// leaving a bit of space above the tallest letters.
+    Top,
+    /// The position is on the baseline of the first line of text,
+    /// i.e. the line most letters "sit" on.
+    Baseline,
+    /// The text is centered on it's position.
+    Center,
+    /// The position is at the bottom of the last line of text,
+    /// i.e. below letters like "g" or "y" that reach below the baseline.
+    Bottom,
+}
+
+/// How far the tallest letters reach above the baseline in format `format`.
+pub fn ascent(format: &TextFormat) -> f32 {
+    "ABCDEFGHIJKLMNOPQRSTUVWXYZbdfhklt"
+        .chars()
+        .map(|c| {
+            let glyph =
+                format.font.glyph(c as u32, format.size, format.bold(), 0.);
+            -glyph.bounds().top
+        })
+        .fold(0., f32::max)
+}
+
+/// How far the lowest letters reach below the baseline in format `format`.
+pub fn descent(format: &TextFormat) -> f32 {
+    "gjpqy"
+        .chars()
+        .map(|c| {
+            let glyph =
+                format.font.glyph(c as u32, format.size, format.bold(), 0.);
+            glyph.bounds().top + glyph.bounds().height
+        })
+        .fold(0., f32::max)
+}
+
+/// The distance between the baselines of two lines of text in format `format`.
+pub fn line_spacing(format: &TextFormat) -> f32 {
+    format.font.line_spacing(format.size)
+}
+
+/// The offset of the center of a line striking through text in format `format`
+/// from the baseline, and the thickness of that line.
+pub fn strikethrough(format: &TextFormat) -> (f32, f32) {
+    // Like SFML, strike through at the middle of the letter "x".
+    let bounds = format
+        .font
+        .glyph('x' as u32, format.size, format.bold(), 0.)
+        .bounds();
+    (
+        bounds.top + bounds.height / 2.,
+        format.font.underline_thickness(format.size),
+    )
+}
+
+/// The width of the widest line of `string`, drawn in format `format`.
+pub fn text_width(format: &TextFormat, string: &str) -> f32 {
+    string
+        .lines()
+        .map(|line| {
+            format.text(line).find_character_pos(line.chars().count()).x
+        })
+        .fold(0., f32::max)
+}
+
+/// The height of the block of text `string`, drawn in format `format`,
+/// from the top of the ascent of the first line to the bottom of the descent of the last line.
+pub fn text_height(format: &TextFormat, string: &str) -> f32 {
+    let lines = string.lines().count().max(1);
+    (lines - 1) as f32 * line_spacing(format) + ascent(format) + descent(format)
+}
+
+/// The offset from the position passed to [`Window::text`] to the top left of the text as drawn by SFML.
+///
+/// [`Window::text`]: crate::window::Window::text
+pub fn align_offset(
+    format: &TextFormat,
+    string: &str,
+    (horizontal, vertical): (HorizontalAlign, VerticalAlign),
+) -> (f32, f32) {
+    let x = match horizontal {
+        HorizontalAlign::Left => 0.,
+        HorizontalAlign::Center => -text_width(format, string) / 2.,
+        HorizontalAlign::Right => -text_width(format, string),
+    };
+
+    // SFML puts the baseline of the first line at `size` below the top of the text.
+    let baseline = format.size as f32;
+    let top = baseline - ascent(format);
+    let y = match vertical {
+        VerticalAlign::Top => 0.,
+        VerticalAlign::Baseline => -baseline,
+        VerticalAlign::Center => -(top + text_height(format, string) / 2.),
+        VerticalAlign::Bottom => -(top + text_height(format, string)),
+    };
+
+    (x, y)
+}
+
+/// The lines of `string` together with the offset from the position passed to [`Window::text`]
+/// to their top left as drawn by SFML.
+/// Unlike for [`align_offset`], each line is aligned horizontally on it's own.
+///
+/// [`Window::text`]: crate::window::Window::text
+pub fn align_lines<'s>(
+    format: &TextFormat,
+    string: &'s str,
+    (horizontal, vertical): (HorizontalAlign, VerticalAlign),
+) -> Vec<(&'s str, (f32, f32))> {
+    let (_, y) = align_offset(format, string, (horizontal, vertical));
+    string
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            let (x, _) = align_offset(format, line, (horizontal, vertical));
+            (line, (x, y + i as f32 * line_spacing(format)))
+        })
+        .collect()
+}
+
+/// What happens to text drawn with [`Window::text_box`] that does not fit into it's box.
+/// Set with [`Window::text_overflow`].
+///
+/// [`Window::text_box`]: crate::window::Window::text_box
+/// [`Window::text_overflow`]: crate::window::Window::text_overflow
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TextOverflow {
+    /// Lines that do not fit into the box are left out.
+    Clip,
+    /// Lines that do not fit into the box are left out,
+    /// and the last line that does fit ends with an ellipsis ("…").
+    Ellipsis,
+}
+
+/// Break `string` into lines no wider than `width`, when drawn in format `format`.
+/// Lines are broken between words where possible, and at line breaks (`\n`) in `string`.
+pub fn wrap(format: &TextFormat, string: &str, width: f32) -> Vec<String> {
+    let fits = |line: &str| text_width(format, line) <= width;
+
+    let mut lines = Vec::new();
+    for paragraph in string.split('\n') {
+        let mut line = String::new();
+        for word in paragraph.split_whitespace() {
+            let candidate = if line.is_empty() {
+                word.to_string()
+            } else {
+                format!("{} {}", line, word)
+            };
+            if fits(&candidate) {
+                line = candidate;
+                continue;
+            }
+            if !line.is_empty() {
+                lines.push(std::mem::take(&mut line));
+            }
+            // Words that are too long for a line on their own are broken between characters.
+            for c in word.chars() {
+                line.push(c);
+                if !fits(&line) && line.chars().count() > 1 {
+                    line.pop();
+                    lines.push(std::mem::take(&mut line));
+                    line.push(c);
+                }
+            }
+        }
+        lines.push(line);
+    }
+    lines
+}
+
+/// Shorten `line` until it fits into `width` with an ellipsis appended, and append the ellipsis.
+fn ellipsize(format: &TextFormat, line: &str, width: f32) -> String {
+    let mut line = line.trim_end().to_string();
+    loop {
+        let candidate = format!("{}…", line);
+        if line.is_empty() || text_width(format, &candidate) <= width {
+            return candidate;
+        }
+        line.pop();
+        line = line.trim_end().to_string();
+    }
+}
+
+/// Lay out `string` in a box of size `(width, height)`, as described for [`Window::text_box`].
+/// Returns the lines of text together with the position of their top left
+/// as drawn by SFML, relative to the top left of the box.
+///
+/// [`Window::text_box`]: crate::window::Window::text_box
+pub fn layout_box(
+    format: &TextFormat,
+    string: &str,
+    (width, height): (f32, f32),
+    line_spacing: f32,
+    overflow: TextOverflow,
+    (horizontal, vertical): (HorizontalAlign, VerticalAlign),
+) -> Vec<(String, (f32, f32))> {
+    let (ascent, descent) = (ascent(format), descent(format));
+    let advance = self::line_spacing(format) * line_spacing;
+
+    let mut lines = wrap(format, string, width);
+    let fitting = if height < ascent + descent {
+        0
+    } else if advance > 0. {
+        ((height - ascent - descent) / advance).floor() as usize + 1
+    } else {
+        lines.len()
+    };
+    if lines.len() > fitting {
+        lines.truncate(fitting);
+        if let (TextOverflow::Ellipsis, Some(last)) =
+            (overflow, lines.last_mut())
+        {
+            *last = ellipsize(format, last, width);
+        }
+    }
+
+    let block_height = match lines.len() {
+        0 => 0.,
+        n => (n - 1) as f32 * advance + ascent + descent,
+    };
+    let top = match vertical {
+        VerticalAlign::Top | VerticalAlign::Baseline => 0.,
+        VerticalAlign::Center => (height - block_height) / 2.,
+        VerticalAlign::Bottom => height - block_height,
+    };
+
+    lines
+        .into_iter()
+        .enumerate()
+        .map(|(i, line)| {
+            let x = match horizontal {
+                HorizontalAlign::Left => 0.,
+                HorizontalAlign::Center => {
+                    (width - text_width(format, &line)) / 2.
+                }
+                HorizontalAlign::Right => width - text_width(format, &line),
+            };
+            // SFML puts the baseline at `size` below the top of the text.
+            let baseline = top + ascent + i as f32 * advance;
+            (line, (x, baseline - format.size as f32))
+        })
+        .collect()
+}
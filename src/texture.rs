@@ -1,49 +1,83 @@
-use sfml::graphics::Texture as SfmlTexture;
-use sfml::SfBox;
-
-/// A global static array containing all textures that have been loaded during the runtime of the program.
-/// Should not be accesses directly outside this module.
-/// # Excuses
-/// So, this isn't a pretty look, I know.
-/// The problem is, as far as I can tell, there is no way to tell Rust's lifetime rules that I just want to give
-/// an existing [`RectangleShape`] a reference to a texture for just the scope of one [`Window::texture`] call.
-/// Neither can I create a bundle of the Shape and it's Texture in one object to align their lifetimes.
-/// Therefore, to ensure than all Textures always outlive the Shape that potentially is referencing them,
-/// I have to make the textures `static`. Hence, this ungodly bunch of code.
-/// If there is a prettier solution that I have overlooked, this should be changed.
-static mut TEXTURE_STORE: Option<Vec<SfBox<SfmlTexture>>> = None;
-pub fn init_texture_store() {
-    unsafe {
-        if TEXTURE_STORE.is_none() {
-            TEXTURE_STORE = Some(Vec::new());
+use sfml::{
+    graphics::{
+        BlendMode, Image, RenderStates, RenderTarget, RenderTexture, Sprite,
+        Texture as SfmlTexture, Transformable,
+    },
+    SfBox,
+};
+
+use crate::{
+    assets::{with_assets, with_assets_mut},
+    error::{
+        check_file_exists, check_pixel_data, check_texture_extension, Error,
+    },
+};
+
+/// A texture in the store, which is either a plain texture,
+/// or the texture of a canvas (See [`Window::create_canvas`]).
+/// The render texture of a canvas is taken out of the store while it is drawn to.
+///
+/// [`Window::create_canvas`]: crate::window::Window::create_canvas
+pub enum StoredTexture {
+    Texture(SfBox<SfmlTexture>),
+    Canvas(Option<RenderTexture>),
+}
+
+impl StoredTexture {
+    /// The SFML texture, or `None` for a canvas that is being drawn to.
+    pub fn sfml_texture(&self) -> Option<&SfmlTexture> {
+        match self {
+            StoredTexture::Texture(t) => Some(t),
+            StoredTexture::Canvas(rt) => rt.as_ref().map(|rt| rt.texture()),
         }
     }
-}
 
-pub fn texture_store(texture: Texture) -> Option<&'static SfBox<SfmlTexture>> {
-    unsafe {
-        if let Some(textures) = &TEXTURE_STORE {
-            Some(&textures[texture.index])
-        } else {
-            None
+    pub fn set_smooth(&mut self, smooth: bool) {
+        match self {
+            StoredTexture::Texture(t) => t.set_smooth(smooth),
+            StoredTexture::Canvas(Some(rt)) => rt.set_smooth(smooth),
+            StoredTexture::Canvas(None) => {}
         }
     }
-}
 
-pub fn texture_store_add(texture: SfBox<SfmlTexture>) -> Option<Texture> {
-    unsafe {
-        if let Some(textures) = &mut TEXTURE_STORE {
-            textures.push(texture);
-            Some(Texture {
-                index: textures.len() - 1,
-            })
-        } else {
-            None
+    pub fn set_repeated(&mut self, repeated: bool) {
+        match self {
+            StoredTexture::Texture(t) => t.set_repeated(repeated),
+            StoredTexture::Canvas(Some(rt)) => rt.set_repeated(repeated),
+            StoredTexture::Canvas(None) => {}
+        }
+    }
+
+    /// Replace the pixels of the texture with `image`, with it's top left at pixel `(x, y)`.
+    pub fn update_from_image(&mut self, image: &Image, x: u32, y: u32) {
+        match self {
+            StoredTexture::Texture(t) => t.update_from_image(image, x, y),
+            StoredTexture::Canvas(Some(rt)) => {
+                // The texture of a canvas can not be written to directly,
+                // so the image is drawn onto it instead, replacing what is there.
+                if let Some(texture) = SfmlTexture::from_image(image) {
+                    let mut sprite = Sprite::with_texture(&texture);
+                    sprite.set_position((x as f32, y as f32));
+                    let mut states = RenderStates::DEFAULT;
+                    states.set_blend_mode(BlendMode::NONE);
+                    rt.draw_with_renderstates(&sprite, &states);
+                    rt.display();
+                }
+            }
+            StoredTexture::Canvas(None) => {}
         }
     }
 }
 
-/// A texture object returned by [`Window::load_texture`], that can be passed to [`Window::texture`] to draw the texture to the screen.
+/// Load the SFML texture from the image file at `path`.
+pub fn texture_from_file(path: &str) -> Result<SfBox<SfmlTexture>, Error> {
+    check_file_exists(path)?;
+    check_texture_extension(path)?;
+    SfmlTexture::from_file(path)
+        .ok_or_else(|| Error::Decode(format!("texture '{}'", path)))
+}
+
+/// A texture object returned by [`Window::load_texture`] or [`Window::create_texture`], that can be passed to [`Window::texture`] to draw the texture to the screen.
 /// # Examples
 /// ```
 /// let mut pg = Window::new_fullscreen();
@@ -57,20 +91,128 @@ pub fn texture_store_add(texture: SfBox<SfmlTexture>) -> Option<Texture> {
 ///
 /// [`Window::texture`]: crate::window::Window::texture
 /// [`Window::load_texture`]: crate::window::Window::load_texture
-#[derive(Clone, Copy)]
+/// [`Window::create_texture`]: crate::window::Window::create_texture
+#[derive(Clone, Copy, PartialEq, Eq)]
 pub struct Texture {
     pub index: usize,
+    /// Tells apart textures that were loaded one after another into the same slot,
+    /// after the earlier one was unloaded with [`Window::unload_texture`].
+    ///
+    /// [`Window::unload_texture`]: crate::window::Window::unload_texture
+    pub generation: u32,
 }
 
 impl Texture {
     /// The width of the texture in pixels.
     pub fn width(&self) -> u32 {
-        texture_store(*self).map(|t| t.size().x).unwrap_or(0)
+        with_assets(|assets| assets.texture(*self).map(|t| t.size().x))
+            .unwrap_or(0)
     }
 
     /// The height of the texture in pixels.
     pub fn height(&self) -> u32 {
-        texture_store(*self).map(|t| t.size().y).unwrap_or(0)
+        with_assets(|assets| assets.texture(*self).map(|t| t.size().y))
+            .unwrap_or(0)
+    }
+
+    /// Replace the pixels of the texture with `pixels`,
+    /// which holds four bytes (red, green, blue, alpha) per pixel, row by row.
+    /// Returns an error if `pixels` does not have the size of the texture, in which case nothing is changed.
+    /// Since drawing happens at the end of the frame, all draws of the texture in the current frame
+    /// show the pixels as they were last updated.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut pg = Window::new(800, 600, "Window Title");
+    /// let noise = pg.create_texture(64, 64, &[0; 64 * 64 * 4]).unwrap();
+    /// let mut pixels = vec![255; 64 * 64 * 4];
+    /// loop {
+    ///     for (i, p) in pixels.chunks_mut(4).enumerate() {
+    ///         p[0] = ((i as f32 + pg.time() * 100.) % 256.) as u8;
+    ///     }
+    ///     noise.update(&pixels).unwrap();
+    ///     pg.texture((0., 0.), noise, 600., 600.);
+    ///     pg.update();
+    /// }
+    /// ```
+    pub fn update(&self, pixels: &[u8]) -> Result<(), Error> {
+        self.update_region(pixels, 0, 0, self.width(), self.height())
+    }
+
+    /// Replace the pixels in the region of width `width` and height `height`,
+    /// with it's top left at pixel `(x, y)`, with `pixels` (See [`Texture::update`]).
+    /// Returns an error if the texture has been unloaded, the region does not lie within the texture,
+    /// or `pixels` does not have the size of the region, in which case nothing is changed.
+    pub fn update_region(
+        &self,
+        pixels: &[u8],
+        x: u32,
+        y: u32,
+        width: u32,
+        height: u32,
+    ) -> Result<(), Error> {
+        let size =
+            with_assets(|assets| Some(assets.texture(*self).map(|t| t.size())))
+                .ok_or(Error::StoreNotInitialized)?
+                .ok_or(Error::Unloaded)?;
+        let fits = x.checked_add(width).is_some_and(|r| r <= size.x)
+            && y.checked_add(height).is_some_and(|b| b <= size.y);
+        if !fits {
+            return Err(Error::RegionOutOfBounds);
+        }
+        check_pixel_data(pixels, width, height)?;
+        let image = Image::create_from_pixels(width, height, pixels)
+            .ok_or_else(|| Error::Decode(String::from("pixel data")))?;
+        with_assets_mut(|assets| {
+            let t = assets.stored_texture_mut(*self)?;
+            t.update_from_image(&image, x, y);
+            Some(())
+        })
+        .ok_or(Error::Unloaded)
+    }
+
+    /// Set whether the texture is smoothed when drawn larger or smaller than it's size.
+    /// Without smoothing, each pixel of the texture is drawn as a sharp square,
+    /// as is usually wanted for pixel art.
+    /// Textures are not smoothed by default.
+    pub fn set_smooth(&self, smooth: bool) {
+        with_assets_mut(|assets| {
+            assets
+                .stored_texture_mut(*self)
+                .map(|t| t.set_smooth(smooth))
+        });
+    }
+
+    /// Whether the texture is smoothed (See [`Texture::set_smooth`]).
+    pub fn is_smooth(&self) -> bool {
+        with_assets(|assets| assets.texture(*self).map(|t| t.is_smooth()))
+            .unwrap_or(false)
+    }
+
+    /// Set whether the texture is repeated when drawn with texture coordinates outside of it,
+    /// like with [`Window::texture_offset`] or [`Window::texture_scale`].
+    /// Without repetition, the pixels at the edges of the texture are stretched instead.
+    /// Textures are not repeated by default.
+    ///
+    /// [`Window::texture_offset`]: crate::window::Window::texture_offset
+    /// [`Window::texture_scale`]: crate::window::Window::texture_scale
+    pub fn set_repeated(&self, repeated: bool) {
+        with_assets_mut(|assets| {
+            assets
+                .stored_texture_mut(*self)
+                .map(|t| t.set_repeated(repeated))
+        });
+    }
+
+    /// Whether the texture is repeated (See [`Texture::set_repeated`]).
+    pub fn is_repeated(&self) -> bool {
+        with_assets(|assets| assets.texture(*self).map(|t| t.is_repeated()))
+            .unwrap_or(false)
+    }
+
+    /// The whole texture as a region `(left, top, width, height)` in pixels.
+    pub fn region(&self) -> (i32, i32, i32, i32) {
+        (0, 0, self.width() as i32, self.height() as i32)
     }
 
     /// The aspect ratio of the texture.
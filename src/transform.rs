@@ -0,0 +1,100 @@
+use sfml::graphics::Transform as SfmlTransform;
+
+/// A 2D affine transformation, stored as the top two rows of a 3x3 matrix.
+/// A point `(x, y)` is mapped to `(a*x + c*y + tx, b*x + d*y + ty)`.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Transform {
+    pub a: f32,
+    pub b: f32,
+    pub c: f32,
+    pub d: f32,
+    pub tx: f32,
+    pub ty: f32,
+}
+
+impl Transform {
+    pub const IDENTITY: Transform = Transform {
+        a: 1.,
+        b: 0.,
+        c: 0.,
+        d: 1.,
+        tx: 0.,
+        ty: 0.,
+    };
+
+    /// Combine this transform with `other`, such that `other` is applied first.
+    pub fn then(self, other: Transform) -> Transform {
+        Transform {
+            a: self.a * other.a + self.c * other.b,
+            b: self.b * other.a + self.d * other.b,
+            c: self.a * other.c + self.c * other.d,
+            d: self.b * other.c + self.d * other.d,
+            tx: self.a * other.tx + self.c * other.ty + self.tx,
+            ty: self.b * other.tx + self.d * other.ty + self.ty,
+        }
+    }
+
+    pub fn translate(self, x: f32, y: f32) -> Transform {
+        self.then(Transform {
+            tx: x,
+            ty: y,
+            ..Self::IDENTITY
+        })
+    }
+
+    /// Rotate by `angle` radians (clockwise, since the y axis points down).
+    pub fn rotate(self, angle: f32) -> Transform {
+        let (sin, cos) = angle.sin_cos();
+        self.then(Transform {
+            a: cos,
+            b: sin,
+            c: -sin,
+            d: cos,
+            ..Self::IDENTITY
+        })
+    }
+
+    pub fn scale(self, x: f32, y: f32) -> Transform {
+        self.then(Transform {
+            a: x,
+            d: y,
+            ..Self::IDENTITY
+        })
+    }
+
+    /// Shear by the angles `x` and `y` (in radians) along the respective axes.
+    pub fn shear(self, x: f32, y: f32) -> Transform {
+        self.then(Transform {
+            b: y.tan(),
+            c: x.tan(),
+            ..Self::IDENTITY
+        })
+    }
+
+    /// The largest factor by which the transform stretches lengths.
+    pub fn scale_factor(&self) -> f32 {
+        (self.a * self.a + self.b * self.b)
+            .max(self.c * self.c + self.d * self.d)
+            .sqrt()
+    }
+
+    /// Apply the transform to the point `p`.
+    pub fn apply(&self, p: (f32, f32)) -> (f32, f32) {
+        (
+            self.a * p.0 + self.c * p.1 + self.tx,
+            self.b * p.0 + self.d * p.1 + self.ty,
+        )
+    }
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl From<Transform> for SfmlTransform {
+    fn from(t: Transform) -> Self {
+        SfmlTransform::new(t.a, t.c, t.tx, t.b, t.d, t.ty, 0., 0., 1.)
+    }
+}
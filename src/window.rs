@@ -1,27 +1,61 @@
-use std::{collections::VecDeque, process::exit};
+use std::{cell::RefCell, collections::VecDeque, process::exit, rc::Rc};
 
+use rust_embed::RustEmbed;
 use sfml::{
     graphics::{
-        CircleShape, Font as SfmlFont, PrimitiveType, RectangleShape,
-        RenderTarget, RenderWindow, Shape, Text, Texture as SfmlTexture,
-        Transformable, Vertex, VertexArray,
+        blend_mode::{Equation, Factor},
+        BlendMode, CircleShape, Image, IntRect, PrimitiveType, RectangleShape,
+        RenderStates, RenderTarget, RenderTexture, RenderWindow, Shape,
+        Sprite as SfmlSprite, TextStyle, Texture as SfmlTexture, Transformable,
+        Vertex, VertexArray,
     },
     system::Clock,
     window::{mouse::Button, Event, Key, Style, VideoMode},
 };
 
 use crate::{
+    animation::Animation,
+    assets::{shared_assets, Assets},
+    canvas::Canvas,
+    capture::{frame_file_name, Recording},
     color::Color,
-    font::{
-        default_font, font_store, font_store_add, init_default_font,
-        init_font_store, Font,
+    curve::{flatten_bezier, flatten_curve},
+    error::{
+        check_font_extension, check_image_save_extension, check_pixel_data,
+        check_texture_extension, Error,
     },
+    font::{Font, StoredFont},
+    geometry::{
+        arc_points, circle_segments, ellipse_points, outline,
+        rounded_rectangle_points, stroke, triangulate, LineCap, LineJoin,
+    },
+    hot_reload::{reload_changed_files, Asset, WatchedFile},
     input::InputState,
-    render_parameters::RenderParameterState,
-    shape::{RenderTask, ShapeStore, Shapes},
-    texture::{init_texture_store, texture_store, texture_store_add, Texture},
+    raster::{warn_not_drawn, Raster},
+    render_parameters::{DrawMode, RenderParameterState},
+    rich_text::{layout, RichText},
+    shape::{ArcMode, RenderTask, ShapeMode, ShapeStore, ShapeVertex, Shapes},
+    sprite::Sprite,
+    text::{
+        align_lines, align_offset, ascent, descent, layout_box, text_height,
+        text_width, HorizontalAlign, TextFormat, TextOverflow, VerticalAlign,
+    },
+    texture::{texture_from_file, Texture},
+    transform::Transform,
 };
 
+/// Blending for drawing a canvas whose colors are premultiplied by their alpha, like the persistent canvas.
+/// Drawing shapes onto a transparent canvas with alpha blending already multiplies their colors by their alpha,
+/// so it must not be done again when drawing the canvas.
+const PREMULTIPLIED_ALPHA: BlendMode = BlendMode::new(
+    Factor::One,
+    Factor::OneMinusSrcAlpha,
+    Equation::Add,
+    Factor::One,
+    Factor::OneMinusSrcAlpha,
+    Equation::Add,
+);
+
 /// The core type of the Pronto Graphics library.
 /// All drawing and keyboard/mouse interaction happens through an instance of [`Window`].
 /// It has to be updated every frame  with [`Window::update`] for drawings to be rendered and the keyboard/mouse state to be updated.
@@ -34,36 +68,43 @@ use crate::{
 ///     pg.update(); // Update for drawing to appear
 /// }
 /// ```
-pub struct Window<'a> {
-    window: RenderWindow,
+pub struct Window {
+    surface: Surface,
     input_state: InputState,
     render_queue: VecDeque<RenderTask>,
     background_color: Color,
     font: Option<Font>,
     render_parameter_state: RenderParameterState,
-    shape_store: ShapeStore<'a>,
+    shape_store: ShapeStore,
+    assets: Rc<RefCell<Assets>>,
+    transform: Transform,
+    transform_stack: Vec<Transform>,
+    shape_mode: Option<ShapeMode>,
+    shape_vertices: Vec<ShapeVertex>,
+    shape_texture: Option<Texture>,
+    frame_cleared: bool,
+    pixels: Vec<Color>,
+    pixels_width: u32,
+    pixel_texture: Option<Texture>,
+    persistent_canvas: Option<Canvas>,
+    save_frame_paths: Vec<String>,
+    recording: Option<Recording>,
+    recording_hotkey: Option<(Key, String, f32)>,
+    frame_count: u64,
+    hot_reload: bool,
+    watched_files: Vec<WatchedFile>,
     deltatime_clock: Clock,
     deltatime: f32,
-    runtime_clock: Clock,
     runtime: f32,
 }
 
-impl Window<'_> {
-    fn new_from_renderwindow(window: RenderWindow) -> Self {
-        init_texture_store();
-        init_default_font();
-        init_font_store();
-
-        let mut circle_shape = CircleShape::new(0., 32);
-        circle_shape.set_outline_thickness(1.);
-        let mut rectangle_shape = RectangleShape::new();
-        rectangle_shape.set_outline_thickness(1.);
-
-        let text =
-            default_font().map(|default_font| Text::new("", default_font, 16));
+impl Window {
+    fn new_from_surface(surface: Surface) -> Self {
+        let circle_shape = CircleShape::new(0., 32);
+        let rectangle_shape = RectangleShape::new();
 
         Self {
-            window,
+            surface,
             input_state: InputState::new(),
             render_queue: VecDeque::new(),
             background_color: Color::LIGHT_GRAY,
@@ -72,10 +113,24 @@ impl Window<'_> {
             shape_store: ShapeStore {
                 circle: circle_shape,
                 rectangle: rectangle_shape,
-                texture: RectangleShape::new(),
-                text,
             },
-            runtime_clock: Clock::start(),
+            assets: shared_assets(),
+            transform: Transform::IDENTITY,
+            transform_stack: Vec::new(),
+            shape_mode: None,
+            shape_vertices: Vec::new(),
+            shape_texture: None,
+            frame_cleared: false,
+            pixels: Vec::new(),
+            pixels_width: 0,
+            pixel_texture: None,
+            persistent_canvas: None,
+            save_frame_paths: Vec::new(),
+            recording: None,
+            recording_hotkey: None,
+            frame_count: 0,
+            hot_reload: false,
+            watched_files: Vec::new(),
             deltatime_clock: Clock::start(),
             deltatime: 1. / 60., // So that we don't get problems in the first frame
             runtime: 0.,
@@ -95,7 +150,7 @@ impl Window<'_> {
         window.set_vertical_sync_enabled(true);
         window.set_key_repeat_enabled(false);
 
-        Self::new_from_renderwindow(window)
+        Self::new_from_surface(Surface::Screen(window))
     }
 
     /// Create a new fullscreen window.
@@ -111,109 +166,241 @@ impl Window<'_> {
         window.set_vertical_sync_enabled(true);
         window.set_key_repeat_enabled(false);
 
-        Self::new_from_renderwindow(window)
+        Self::new_from_surface(Surface::Screen(window))
+    }
+
+    /// Create a new headless window of size (`width`, `height`), which is not shown on the screen,
+    /// for rendering without a display or graphics card, e.g. on a server or in tests.
+    /// It is drawn to just like any other window, but in software,
+    /// and the drawn frame can be read with [`Window::capture`], or saved with [`Window::save_frame`].
+    /// Textures and text need a graphics card, and are skipped in a headless window with a warning printed once,
+    /// as are the window's pixels drawn with [`Window::update_pixels`].
+    /// Measuring text, like with [`Window::text_width`], gives a size of zero,
+    /// and loading or creating textures and canvases returns [`Error::Headless`].
+    /// A headless window has no keyboard or mouse input,
+    /// and [`Window::update`] does not wait for vertical sync.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut pg = Window::new_headless(256, 256);
+    /// pg.fill_color(Color::BLUE);
+    /// pg.circle((128., 128.), 100.);
+    /// pg.save_frame("thumbnail.png").unwrap();
+    /// pg.update();
+    /// ```
+    pub fn new_headless(width: u32, height: u32) -> Self {
+        Self::new_from_surface(Surface::Headless {
+            frame: Raster::new(width, height),
+            persistent: None,
+        })
     }
 
     /// Has to be called every frame for drawings to appear on the screen and keyboard/mouse to be updated.
     /// Note that this function will block for vertical sync.
     pub fn update(&mut self) {
+        if self.hot_reload {
+            reload_changed_files(
+                &mut self.assets.borrow_mut(),
+                &mut self.watched_files,
+            );
+        }
         self.update_events();
+        self.update_recording_hotkey();
         self.update_draw();
 
-        self.deltatime = self.deltatime_clock.restart().as_seconds();
-        self.runtime = self.runtime_clock.elapsed_time().as_seconds();
+        let deltatime = self.deltatime_clock.restart().as_seconds();
+        // While recording, time passes at the recording's frame rate,
+        // so that the recording plays back at the intended speed.
+        self.deltatime = match &self.recording {
+            Some(recording) => recording.timestep(),
+            None => deltatime,
+        };
+        self.runtime += self.deltatime;
+        self.frame_count += 1;
 
         self.render_parameter_state = Default::default();
+        self.reset_matrix();
     }
 
     fn update_events(&mut self) {
         self.input_state.clear();
-        while let Some(event) = self.window.poll_event() {
+        let Surface::Screen(window) = &mut self.surface else {
+            return;
+        };
+        let mut closed = false;
+        while let Some(event) = window.poll_event() {
             self.input_state.handle_event(event);
             match event {
                 Event::Closed
                 | Event::KeyPressed {
                     code: Key::ESCAPE, ..
-                } => exit(0),
+                } => closed = true,
                 _ => {}
             }
         }
+        if closed {
+            // Exiting skips destructors, so a recording has to be finished first
+            if let Err(e) = self.stop_recording() {
+                eprintln!("{}", e);
+            }
+            exit(0);
+        }
+    }
+
+    fn update_recording_hotkey(&mut self) {
+        let Some((key, path, fps)) = &self.recording_hotkey else {
+            return;
+        };
+        if !self.input_state.key_just_pressed(*key) {
+            return;
+        }
+        let result = if self.recording.is_some() {
+            self.stop_recording()
+        } else {
+            let (path, fps) = (path.clone(), *fps);
+            self.start_recording(&path, fps)
+        };
+        if let Err(e) = result {
+            eprintln!("{}", e);
+        }
     }
 
     fn update_draw(&mut self) {
-        self.window.clear(self.background_color.into());
-        for task in &self.render_queue {
-            let RenderTask {
-                pos,
-                shape,
-                render_parameter_state: color_state,
-            } = task;
-
-            match shape {
-                Shapes::Circle { radius } => {
-                    let s = &mut self.shape_store.circle;
-                    s.set_radius(*radius);
-                    s.set_origin((s.radius(), s.radius()));
-                    s.set_position(*pos);
-                    s.set_fill_color(color_state.fill_color.into());
-                    s.set_outline_color(color_state.outline_color.into());
-                    self.window.draw(s);
-                }
-                Shapes::Rectangle { width, height } => {
-                    let s = &mut self.shape_store.rectangle;
-                    s.set_size((*width, *height));
-                    // s.set_origin((*width / 2., *height / 2.));
-                    s.set_position(*pos);
-                    s.set_fill_color(color_state.fill_color.into());
-                    s.set_outline_color(color_state.outline_color.into());
-                    self.window.draw(s);
-                }
-                Shapes::Lines { coords } => {
-                    let mut va =
-                        VertexArray::new(PrimitiveType::LINES, coords.len());
-                    for (i, v) in coords.iter().enumerate() {
-                        va[i] = Vertex::with_pos_color(
-                            (*v).into(),
-                            color_state.line_color.into(),
-                        );
-                    }
+        self.draw_queue();
+        if !self.save_frame_paths.is_empty() || self.recording.is_some() {
+            self.save_frame_images();
+        }
+        if let Surface::Screen(window) = &mut self.surface {
+            window.display();
+        }
+        self.frame_cleared = false;
+    }
 
-                    self.window.draw(&va);
-                }
-                Shapes::Texture {
-                    texture,
-                    width,
-                    height,
-                } => {
-                    if let Some(tex) = &texture_store(*texture) {
-                        let s = &mut self.shape_store.texture;
-                        s.set_texture(tex, false);
-                        s.set_size((*width, *height));
-                        // s.set_origin((*width / 2., *height / 2.));
-                        s.set_position(*pos);
-                        self.window.draw(s);
+    /// Save the frame drawn so far to the files requested with [`Window::save_frame`]
+    /// and add it to the current recording.
+    fn save_frame_images(&mut self) {
+        let paths = std::mem::take(&mut self.save_frame_paths);
+        let Some(image) = self.window_image() else {
+            eprintln!("Failed to capture the frame for saving.");
+            return;
+        };
+        for path in paths {
+            if !image.save_to_file(&path) {
+                eprintln!("{}", Error::Save(path));
+            }
+        }
+        let result = self.recording.as_mut().map(|r| r.add_frame(&image));
+        if let Some(Err(e)) = result {
+            // Don't keep trying to record after failing once
+            eprintln!("{}", e);
+            self.recording = None;
+        }
+    }
+
+    /// Draw everything in the render queue to the window, without displaying it yet,
+    /// clearing the window first if that has not happened yet this frame.
+    /// With persistent drawing, what would be drawn to the window is drawn to the persistent canvas,
+    /// which is then drawn over the background instead.
+    fn draw_queue(&mut self) {
+        if !self.frame_cleared {
+            self.surface.clear(self.background_color);
+            self.frame_cleared = true;
+        }
+        // Draw the tasks in runs with the same target, since a canvas is taken out of the assets
+        // while being drawn to.
+        let mut tasks = std::mem::take(&mut self.render_queue);
+        while let Some(first) = tasks.front() {
+            let target_of = |t: &RenderTask| {
+                t.render_parameter_state.target.or(self.persistent_canvas)
+            };
+            let target = target_of(first);
+            let n = tasks.iter().take_while(|t| target_of(t) == target).count();
+            let run = tasks.drain(..n);
+            match target {
+                None => match &mut self.surface {
+                    Surface::Screen(window) => {
+                        let assets = self.assets.borrow();
+                        for task in run {
+                            draw_task(
+                                window,
+                                &mut self.shape_store,
+                                &assets,
+                                &task,
+                            );
+                        }
                     }
-                }
-                Shapes::Text { string, font } => {
-                    if let Some(t) = &mut self.shape_store.text {
-                        let sfml_font = font
-                            .and_then(font_store) // Get custom font from font store
-                            .or_else(default_font); // Or use the default font
-                        if let Some(sfml_font) = sfml_font {
-                            // If some kind of font was found, set it
-                            t.set_font(sfml_font)
+                    Surface::Headless { frame, persistent } => {
+                        for task in run {
+                            match (persistent.as_mut(), &task.shape) {
+                                // Keep the colors of the persistent layer premultiplied,
+                                // like those of the persistent canvas
+                                (Some(layer), Shapes::Clear { color }) => {
+                                    layer.clear(color.premultiplied())
+                                }
+                                (Some(layer), _) => layer.draw_task(&task),
+                                (None, _) => frame.draw_task(&task),
+                            }
                         }
-                        t.set_character_size(color_state.font_size);
-                        t.set_string(string);
-                        t.set_fill_color(color_state.font_color.into());
-                        t.set_position(*pos);
-                        self.window.draw(t);
+                    }
+                },
+                Some(canvas) => {
+                    let persistent = Some(canvas) == self.persistent_canvas;
+                    let taken = self.assets.borrow_mut().take_canvas(canvas);
+                    if let Some(mut render_texture) = taken {
+                        let assets = self.assets.borrow();
+                        for task in run {
+                            // Keep the colors of the persistent canvas premultiplied (See `PREMULTIPLIED_ALPHA`)
+                            if let (true, Shapes::Clear { color }) =
+                                (persistent, &task.shape)
+                            {
+                                render_texture
+                                    .clear(color.premultiplied().into());
+                                continue;
+                            }
+                            draw_task(
+                                &mut render_texture,
+                                &mut self.shape_store,
+                                &assets,
+                                &task,
+                            );
+                        }
+                        drop(assets);
+                        render_texture.display();
+                        self.assets
+                            .borrow_mut()
+                            .return_canvas(canvas, render_texture);
+                    }
+                }
+            }
+        }
+        // Keep the allocation of the queue around
+        self.render_queue = tasks;
+
+        match &mut self.surface {
+            Surface::Screen(window) => {
+                if let Some(canvas) = self.persistent_canvas {
+                    window.clear(self.background_color.into());
+                    if let Some(texture) =
+                        self.assets.borrow().texture(canvas.texture())
+                    {
+                        let mut states = RenderStates::DEFAULT;
+                        states.set_blend_mode(PREMULTIPLIED_ALPHA);
+                        window.draw_with_renderstates(
+                            &SfmlSprite::with_texture(texture),
+                            &states,
+                        );
                     }
                 }
             }
+            Surface::Headless {
+                frame,
+                persistent: Some(layer),
+            } => {
+                frame.clear(self.background_color);
+                frame.draw_raster(layer);
+            }
+            Surface::Headless { .. } => {}
         }
-        self.render_queue.clear();
-        self.window.display();
     }
 
     /// Set the background color of the window.
@@ -223,6 +410,94 @@ impl Window<'_> {
         self.background_color = color.into();
     }
 
+    /// Set whether drawings persist across frames, instead of the window being cleared
+    /// to the background color at the beginning of every frame.
+    /// With persistent drawing, everything drawn accumulates, like in Processing sketches
+    /// that do not call `background()`, e.g. for paint programs or trails.
+    /// It can be wiped with [`Window::clear`].
+    /// The drawings are kept on an internal, window-sized canvas (See [`Canvas`]),
+    /// drawn over the background color every frame.
+    /// Disabling persistent drawing discards the drawings.
+    /// Persistent drawing does _not_ reset at the beginning of a new frame and is disabled initially.
+    /// Returns an error if the internal canvas could not be created.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut pg = Window::new(800, 600, "Window Title");
+    /// pg.persistent_drawing(true).unwrap();
+    /// loop {
+    ///     if pg.mouse_pressed(Button::LEFT) {
+    ///         pg.circle(pg.mouse_position(), 5.); // Paint with the mouse
+    ///     }
+    ///     if pg.key_just_pressed(Key::C) {
+    ///         pg.clear(Color::TRANSPARENT); // Wipe the painting
+    ///     }
+    ///     pg.update();
+    /// }
+    /// ```
+    pub fn persistent_drawing(&mut self, enabled: bool) -> Result<(), Error> {
+        if let Surface::Headless { frame, persistent } = &mut self.surface {
+            if enabled != persistent.is_some() {
+                *persistent = enabled.then(|| {
+                    let mut layer = Raster::new(frame.width(), frame.height());
+                    layer.clear(Color::TRANSPARENT);
+                    layer
+                });
+            }
+            return Ok(());
+        }
+        match (enabled, self.persistent_canvas) {
+            (true, None) => {
+                let (width, height) = self.surface.size();
+                self.persistent_canvas =
+                    Some(self.create_canvas(width, height)?);
+            }
+            (false, Some(canvas)) => {
+                self.persistent_canvas = None;
+                self.unload_texture(canvas.texture());
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Set the canvas that all later draw calls draw to, instead of the window (See [`Canvas`]).
+    /// A value of `None` draws to the window again.
+    /// The target is reset at the beginning of a new frame to a default value of `None`.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut pg = Window::new(800, 600, "Window Title");
+    /// let trail = pg.create_canvas(800, 600).unwrap();
+    /// loop {
+    ///     pg.canvas(Some(trail));
+    ///     pg.circle(pg.mouse_position(), 10.); // Stays on the canvas across frames
+    ///     pg.canvas(None);
+    ///
+    ///     pg.texture((0., 0.), trail.texture(), 800., 600.);
+    ///     pg.update();
+    /// }
+    /// ```
+    pub fn canvas(&mut self, canvas: Option<Canvas>) {
+        self.render_parameter_state.target = canvas;
+    }
+
+    /// Clear whatever is currently drawn to (the window or a canvas set with [`Window::canvas`])
+    /// to `color`, covering everything drawn to it before.
+    /// With persistent drawing (See [`Window::persistent_drawing`]), clearing the window
+    /// clears the persistent drawings, so that clearing to [`Color::TRANSPARENT`]
+    /// reveals the background color.
+    pub fn clear<C: Into<Color>>(&mut self, color: C) {
+        self.render_queue.push_back(RenderTask {
+            pos: (0., 0.),
+            shape: Shapes::Clear {
+                color: color.into(),
+            },
+            render_parameter_state: self.render_parameter_state,
+            transform: self.transform,
+        });
+    }
+
     /// Set the fill color for drawing shapes like [`Window::circle`].
     /// The fill color is reset at the beginning of a new frame to a default value of [`Color::WHITE`].
     pub fn fill_color<C: Into<Color>>(&mut self, color: C) {
@@ -235,12 +510,140 @@ impl Window<'_> {
         self.render_parameter_state.outline_color = color.into();
     }
 
+    /// Set the thickness of the outline for drawing shapes like [`Window::circle`].
+    /// The outline is drawn with the color set with [`Window::outline_color`].
+    /// A thickness of `0` disables the outline.
+    /// The outline thickness is reset at the beginning of a new frame to a default value of `1`.
+    pub fn outline_thickness(&mut self, thickness: f32) {
+        self.render_parameter_state.outline_thickness = thickness;
+    }
+
     /// Set the line color for drawing lines with [`Window::line`].
     /// The line color is reset at the beginning of a new frame to a default value of [`Color::BLACK`].
     pub fn line_color<C: Into<Color>>(&mut self, color: C) {
         self.render_parameter_state.line_color = color.into();
     }
 
+    /// Set the line width for drawing lines with [`Window::line`] and [`Window::polyline`].
+    /// Lines with a width of up to `1` are drawn as thin hairlines,
+    /// wider lines are drawn with the line cap and join set with [`Window::line_cap`] and [`Window::line_join`].
+    /// The line width is reset at the beginning of a new frame to a default value of `1`.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut pg = Window::new(800, 600, "Window Title");
+    /// loop {
+    ///     pg.line_width(8.);
+    ///     pg.line_cap(LineCap::Round);
+    ///     pg.line((100., 100.), (300., 200.));
+    ///     pg.update();
+    /// }
+    /// ```
+    pub fn line_width(&mut self, width: f32) {
+        self.render_parameter_state.line_width = width;
+    }
+
+    /// Set how the ends of wide lines are drawn (See [`LineCap`]).
+    /// The line cap is reset at the beginning of a new frame to a default value of [`LineCap::Butt`].
+    pub fn line_cap(&mut self, cap: LineCap) {
+        self.render_parameter_state.line_cap = cap;
+    }
+
+    /// Set how the corners of wide lines drawn with [`Window::polyline`] are drawn (See [`LineJoin`]).
+    /// The line join is reset at the beginning of a new frame to a default value of [`LineJoin::Miter`].
+    pub fn line_join(&mut self, join: LineJoin) {
+        self.render_parameter_state.line_join = join;
+    }
+
+    /// Set which parts of arcs drawn with [`Window::arc`] are filled and outlined (See [`ArcMode`]).
+    /// The arc mode is reset at the beginning of a new frame to a default value of [`ArcMode::Pie`].
+    pub fn arc_mode(&mut self, mode: ArcMode) {
+        self.render_parameter_state.arc_mode = mode;
+    }
+
+    /// Set how the position and size passed to [`Window::rectangle`], [`Window::square`]
+    /// and [`Window::rounded_rectangle`] are interpreted (See [`DrawMode`]).
+    /// The rect mode is reset at the beginning of a new frame to a default value of [`DrawMode::Corner`].
+    ///
+    /// # Examples
+    /// ```
+    /// let mut pg = Window::new(800, 600, "Window Title");
+    /// loop {
+    ///     pg.rect_mode(DrawMode::Center);
+    ///     pg.rectangle((400., 300.), 100., 50.); // Centered in the window
+    ///     pg.update();
+    /// }
+    /// ```
+    pub fn rect_mode(&mut self, mode: DrawMode) {
+        self.render_parameter_state.rect_mode = mode;
+    }
+
+    /// Set how the position and radii passed to [`Window::circle`], [`Window::ellipse`]
+    /// and [`Window::arc`] are interpreted (See [`DrawMode`]).
+    /// Like in Processing, with [`DrawMode::Corner`] and [`DrawMode::Center`],
+    /// the "radii" are taken as the width and height of the ellipse instead.
+    /// The ellipse mode is reset at the beginning of a new frame to a default value of [`DrawMode::Radius`],
+    /// so that by default, circles and ellipses are drawn around their position with the given radii.
+    pub fn ellipse_mode(&mut self, mode: DrawMode) {
+        self.render_parameter_state.ellipse_mode = mode;
+    }
+
+    /// Set how the position and size passed to [`Window::texture`] and [`Window::texture_`]
+    /// are interpreted (See [`DrawMode`]).
+    /// The image mode is reset at the beginning of a new frame to a default value of [`DrawMode::Corner`].
+    pub fn image_mode(&mut self, mode: DrawMode) {
+        self.render_parameter_state.image_mode = mode;
+    }
+
+    /// Set the color textures drawn with functions like [`Window::texture`] are tinted with,
+    /// i.e. multiplied by.
+    /// A tint of `Color::WHITE.with_alpha(127)` draws textures half transparent.
+    /// The tint also applies to textured shapes (See [`Window::shape_texture`]).
+    /// The tint is reset at the beginning of a new frame to a default value of [`Color::WHITE`],
+    /// i.e. no tint.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut pg = Window::new(800, 600, "Window Title");
+    /// let my_texture = pg.load_texture("my_texture.png").unwrap();
+    /// loop {
+    ///     pg.tint(Color::RED.with_alpha(200));
+    ///     pg.texture((100., 100.), my_texture, 200., 200.);
+    ///     pg.update();
+    /// }
+    /// ```
+    pub fn tint<C: Into<Color>>(&mut self, color: C) {
+        self.render_parameter_state.tint = color.into();
+    }
+
+    /// Move the texture coordinates of textures drawn with functions like [`Window::texture`]
+    /// by `(x, y)` pixels of the texture, like for a scrolling background.
+    /// Combine with [`Texture::set_repeated`] to fill the space beyond the edge of the texture.
+    /// The texture offset is reset at the beginning of a new frame to a default value of `(0, 0)`.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut pg = Window::new(800, 600, "Window Title");
+    /// let clouds = pg.load_texture("clouds.png").unwrap();
+    /// clouds.set_repeated(true);
+    /// loop {
+    ///     pg.texture_offset(pg.time() * 20., 0.);
+    ///     pg.texture((0., 0.), clouds, 800., 600.);
+    ///     pg.update();
+    /// }
+    /// ```
+    pub fn texture_offset(&mut self, x: f32, y: f32) {
+        self.render_parameter_state.texture_offset = (x, y);
+    }
+
+    /// Scale the texture coordinates of textures drawn with functions like [`Window::texture`]
+    /// by `(x, y)`, such that e.g. a scale of `2` fits the texture into the drawn area twice along that axis.
+    /// Combine with [`Texture::set_repeated`] to fill the space beyond the edge of the texture.
+    /// The texture scale is reset at the beginning of a new frame to a default value of `(1, 1)`.
+    pub fn texture_scale(&mut self, x: f32, y: f32) {
+        self.render_parameter_state.texture_scale = (x, y);
+    }
+
     /// Set the line color for drawing text with [`Window::text`].
     /// The font color is reset at the beginning of a new frame to a default value of [`Color::BLACK`].
     pub fn font_color<C: Into<Color>>(&mut self, color: C) {
@@ -253,6 +656,50 @@ impl Window<'_> {
         self.render_parameter_state.font_size = size;
     }
 
+    /// Set the style (bold, italic, underlined) for drawing text with [`Window::text`].
+    /// Styles can be combined, like `TextStyle::BOLD | TextStyle::ITALIC`.
+    /// The text style is reset at the beginning of a new frame to a default value of [`TextStyle::REGULAR`].
+    pub fn text_style(&mut self, style: TextStyle) {
+        self.render_parameter_state.text_style = style;
+    }
+
+    /// Set how text drawn with [`Window::text`] is aligned relative to it's position
+    /// (See [`HorizontalAlign`] and [`VerticalAlign`]).
+    /// For text with multiple lines, each line is aligned horizontally on it's own.
+    /// The text alignment is reset at the beginning of a new frame to a default value of
+    /// [`HorizontalAlign::Left`] and [`VerticalAlign::Top`].
+    ///
+    /// # Examples
+    /// ```
+    /// let mut pg = Window::new(800, 600, "Window Title");
+    /// loop {
+    ///     pg.text_align(HorizontalAlign::Center, VerticalAlign::Center);
+    ///     pg.text((400., 300.), "Right in the middle!");
+    ///     pg.update();
+    /// }
+    /// ```
+    pub fn text_align(
+        &mut self,
+        horizontal: HorizontalAlign,
+        vertical: VerticalAlign,
+    ) {
+        self.render_parameter_state.text_align = (horizontal, vertical);
+    }
+
+    /// Set the spacing between lines of text drawn with [`Window::text_box`],
+    /// as a factor of the font's natural line spacing.
+    /// The line spacing is reset at the beginning of a new frame to a default value of `1`.
+    pub fn line_spacing(&mut self, spacing: f32) {
+        self.render_parameter_state.line_spacing = spacing;
+    }
+
+    /// Set what happens to text drawn with [`Window::text_box`] that does not fit into it's box
+    /// (See [`TextOverflow`]).
+    /// The text overflow is reset at the beginning of a new frame to a default value of [`TextOverflow::Clip`].
+    pub fn text_overflow(&mut self, overflow: TextOverflow) {
+        self.render_parameter_state.text_overflow = overflow;
+    }
+
     /// Set the font for drawing text with [`Window::text`].
     /// The font does _not_ reset at the beginning of a new frame.
     /// Fonts can be loaded with [`Window::load_font`].
@@ -274,28 +721,113 @@ impl Window<'_> {
         self.font = font
     }
 
+    /// Move the origin of the coordinate system by `offset` for all later draw calls.
+    /// Like all transformations, the translation is combined with the current transform,
+    /// and is reset at the beginning of a new frame.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut pg = Window::new(800, 600, "Window Title");
+    /// loop {
+    ///     pg.translate((400., 300.));
+    ///     pg.circle((0., 0.), 50.); // Drawn at the center of the window
+    ///     pg.update();
+    /// }
+    /// ```
+    pub fn translate(&mut self, offset: (f32, f32)) {
+        self.transform = self.transform.translate(offset.0, offset.1);
+    }
+
+    /// Rotate the coordinate system by `angle` (in radians) around the current origin
+    /// for all later draw calls. Positive angles rotate clockwise.
+    /// The rotation is reset at the beginning of a new frame.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut pg = Window::new(800, 600, "Window Title");
+    /// loop {
+    ///     pg.translate((400., 300.));
+    ///     pg.rotate(pg.time());
+    ///     pg.rectangle((-50., -25.), 100., 50.); // A spinning rectangle
+    ///     pg.update();
+    /// }
+    /// ```
+    pub fn rotate(&mut self, angle: f32) {
+        self.transform = self.transform.rotate(angle);
+    }
+
+    /// Scale the coordinate system by `factor` along the x and y axes
+    /// for all later draw calls.
+    /// The scaling is reset at the beginning of a new frame.
+    pub fn scale(&mut self, factor: (f32, f32)) {
+        self.transform = self.transform.scale(factor.0, factor.1);
+    }
+
+    /// Shear the coordinate system by the angles `angle` (in radians) along the x and y axes
+    /// for all later draw calls.
+    /// The shear is reset at the beginning of a new frame.
+    pub fn shear(&mut self, angle: (f32, f32)) {
+        self.transform = self.transform.shear(angle.0, angle.1);
+    }
+
+    /// Save the current transform, so that it can be restored later with [`Window::pop_matrix`].
+    ///
+    /// # Examples
+    /// ```
+    /// let mut pg = Window::new(800, 600, "Window Title");
+    /// loop {
+    ///     pg.push_matrix();
+    ///     pg.translate((100., 100.));
+    ///     pg.circle((0., 0.), 10.); // Drawn at (100, 100)
+    ///     pg.pop_matrix();
+    ///     pg.circle((0., 0.), 10.); // Drawn at (0, 0)
+    ///     pg.update();
+    /// }
+    /// ```
+    pub fn push_matrix(&mut self) {
+        self.transform_stack.push(self.transform);
+    }
+
+    /// Restore the transform last saved with [`Window::push_matrix`].
+    /// If there is no saved transform, the transform is left unchanged.
+    pub fn pop_matrix(&mut self) {
+        if let Some(transform) = self.transform_stack.pop() {
+            self.transform = transform;
+        }
+    }
+
+    /// Reset the transform to the identity, i.e. undo all translations, rotations, etc.,
+    /// and discard all transforms saved with [`Window::push_matrix`].
+    /// This happens automatically at the beginning of a new frame.
+    pub fn reset_matrix(&mut self) {
+        self.transform = Transform::IDENTITY;
+        self.transform_stack.clear();
+    }
+
     /// Draw a circle at position `pos` with radius `radius`.
-    /// The origin of the circle is at it's center.
+    /// The origin of the circle is at it's center, unless changed with [`Window::ellipse_mode`].
     pub fn circle(&mut self, pos: (f32, f32), radius: f32) {
         self.render_queue.push_back(RenderTask {
             pos,
             shape: Shapes::Circle { radius },
             render_parameter_state: self.render_parameter_state,
+            transform: self.transform,
         })
     }
 
     /// Draw a rectangle at position `pos` with width and height of `(width, height)`.
-    /// The origin of the rectangle is at it's top left.
+    /// The origin of the rectangle is at it's top left, unless changed with [`Window::rect_mode`].
     pub fn rectangle(&mut self, pos: (f32, f32), width: f32, height: f32) {
         self.render_queue.push_back(RenderTask {
             pos,
             shape: Shapes::Rectangle { width, height },
             render_parameter_state: self.render_parameter_state,
+            transform: self.transform,
         })
     }
 
     /// Draw a square at position `pos` with a width and height of `size`.
-    /// The origin of the square is at it's top left.
+    /// The origin of the square is at it's top left, unless changed with [`Window::rect_mode`].
     pub fn square(&mut self, pos: (f32, f32), size: f32) {
         self.render_queue.push_back(RenderTask {
             pos,
@@ -304,117 +836,720 @@ impl Window<'_> {
                 height: size,
             },
             render_parameter_state: self.render_parameter_state,
+            transform: self.transform,
         })
     }
 
-    /// Draw a texture `texture` at position `pos` with width and height of `(width, height)`.
-    /// The origin of the texture is at it's top left.
-    /// Textures can be loaded with [`Window::load_texture`].
+    /// Draw an ellipse at position `pos` with the horizontal and vertical radii `radius_x` and `radius_y`.
+    /// The origin of the ellipse is at it's center, unless changed with [`Window::ellipse_mode`].
+    pub fn ellipse(&mut self, pos: (f32, f32), radius_x: f32, radius_y: f32) {
+        self.render_queue.push_back(RenderTask {
+            pos,
+            shape: Shapes::Ellipse { radius_x, radius_y },
+            render_parameter_state: self.render_parameter_state,
+            transform: self.transform,
+        })
+    }
+
+    /// Draw an arc of a circle at position `pos` with radius `radius`,
+    /// from angle `start` to angle `end` (in radians, clockwise from the positive x axis).
+    /// The origin of the arc is at the center of it's circle, unless changed with [`Window::ellipse_mode`].
+    /// Whether the arc is drawn open, as a chord or as a slice of pie is set with [`Window::arc_mode`].
+    ///
     /// # Examples
     /// ```
-    /// let mut pg = Window::new_fullscreen();
-    /// let my_texture = pg.load_texture("my_texture.png").unwrap();
+    /// let mut pg = Window::new(800, 600, "Window Title");
     /// loop {
-    ///     pg.texture((100., 250.), my_texture, 100., 150.);
-    ///
+    ///     // A Pac-Man
+    ///     pg.fill_color(Color::rgb(0xFF, 0xEE, 0x00));
+    ///     pg.arc((400., 300.), 100., 0.25 * PI, 1.75 * PI);
     ///     pg.update();
     /// }
     /// ```
-    pub fn texture(
+    pub fn arc(&mut self, pos: (f32, f32), radius: f32, start: f32, end: f32) {
+        self.render_queue.push_back(RenderTask {
+            pos,
+            shape: Shapes::Arc { radius, start, end },
+            render_parameter_state: self.render_parameter_state,
+            transform: self.transform,
+        })
+    }
+
+    /// Draw a rectangle with rounded corners at position `pos` with width and height of `(width, height)`,
+    /// and with it's corners rounded off with a radius of `corner_radius`.
+    /// The origin of the rectangle is at it's top left, unless changed with [`Window::rect_mode`].
+    pub fn rounded_rectangle(
         &mut self,
         pos: (f32, f32),
-        texture: Texture,
         width: f32,
         height: f32,
+        corner_radius: f32,
     ) {
         self.render_queue.push_back(RenderTask {
             pos,
-            shape: Shapes::Texture {
-                texture,
+            shape: Shapes::RoundedRectangle {
                 width,
                 height,
+                corner_radius,
             },
             render_parameter_state: self.render_parameter_state,
+            transform: self.transform,
         })
     }
 
-    /// Draw a texture `texture` at position `pos` with width of `width`,
-    /// and height according to the aspect ratio of the texture.
-    /// The origin of the texture is at it's top left.
-    /// Textures can be loaded with [`Window::load_texture`].
+    /// Draw a triangle with the corners `a`, `b` and `c`.
+    /// The triangle is filled with the fill color set with [`Window::fill_color`]
+    /// and outlined with the outline color set with [`Window::outline_color`].
+    pub fn triangle(&mut self, a: (f32, f32), b: (f32, f32), c: (f32, f32)) {
+        self.polygon(&[a, b, c]);
+    }
+
+    /// Draw a quadrilateral with the corners `a`, `b`, `c` and `d`, in that order.
+    /// The quad does not have to be convex.
+    /// It is filled with the fill color set with [`Window::fill_color`]
+    /// and outlined with the outline color set with [`Window::outline_color`].
+    pub fn quad(
+        &mut self,
+        a: (f32, f32),
+        b: (f32, f32),
+        c: (f32, f32),
+        d: (f32, f32),
+    ) {
+        self.polygon(&[a, b, c, d]);
+    }
+
+    /// Draw a polygon with the corners `points`, in that order.
+    /// The polygon may be concave, but should not intersect itself.
+    /// It is filled with the fill color set with [`Window::fill_color`]
+    /// and outlined with the outline color set with [`Window::outline_color`].
+    /// Like for all shapes, the outline lies outside of the polygon.
+    ///
     /// # Examples
     /// ```
-    /// let mut pg = Window::new_fullscreen();
-    /// let my_texture = pg.load_texture("my_texture.png").unwrap();
+    /// let mut pg = Window::new(800, 600, "Window Title");
     /// loop {
-    ///     pg.texture_((100., 250.), my_texture, 100.);
-    ///
+    ///     // An arrow pointing to the right
+    ///     pg.polygon(&[
+    ///         (100., 100.),
+    ///         (200., 150.),
+    ///         (100., 200.),
+    ///         (130., 150.),
+    ///     ]);
     ///     pg.update();
     /// }
     /// ```
-    pub fn texture_(&mut self, pos: (f32, f32), texture: Texture, width: f32) {
+    pub fn polygon(&mut self, points: &[(f32, f32)]) {
+        if points.len() < 3 {
+            return;
+        }
         self.render_queue.push_back(RenderTask {
-            pos,
-            shape: Shapes::Texture {
-                texture,
-                width,
-                height: width / texture.aspect(),
+            pos: (0., 0.),
+            shape: Shapes::Polygon {
+                points: points.to_vec(),
             },
             render_parameter_state: self.render_parameter_state,
+            transform: self.transform,
         })
     }
 
-    /// Draw text `string` at position `pos`.
-    /// The default font size is 16 and can be changed with [`Window::font_size`].
-    /// The default font color is [`Color::BLACK`] and can be changed with [`Window::font_color`].
-    /// Uses the default font built into the library (Processing Sans Pro)
-    /// or the font set with [`Window::font`].
+    /// Begin building a custom shape out of vertices added with [`Window::vertex`],
+    /// which is drawn once [`Window::end_shape`] is called.
+    /// How the vertices are connected is determined by `mode` (See [`ShapeMode`]).
+    ///
     /// # Examples
     /// ```
-    /// let mut pg = Window::new(720, 480, "Window Title");
+    /// let mut pg = Window::new(800, 600, "Window Title");
     /// loop {
-    ///     pg.fill_color(Color::BLACK);
-    ///     pg.text((10., 10.), "Hello World!");
+    ///     pg.begin_shape(ShapeMode::TriangleFan);
+    ///     pg.fill_color(Color::RED);
+    ///     pg.vertex((400., 300.));
+    ///     pg.fill_color(Color::BLUE);
+    ///     pg.vertex((500., 300.));
+    ///     pg.vertex((400., 400.));
+    ///     pg.vertex((300., 300.));
+    ///     pg.end_shape();
+    ///
     ///     pg.update();
     /// }
     /// ```
-    pub fn text(&mut self, pos: (f32, f32), string: &str) {
-        self.render_queue.push_back(RenderTask {
-            pos,
-            shape: Shapes::Text {
-                string: string.to_string(),
-                font: self.font,
-            },
-            render_parameter_state: self.render_parameter_state,
-        })
+    pub fn begin_shape(&mut self, mode: ShapeMode) {
+        self.shape_mode = Some(mode);
+        self.shape_vertices.clear();
+        self.shape_texture = None;
     }
 
-    /// Draw a line from position `from` to position `to`.
-    /// The line's color is set with [`Window::line_color`].
-    pub fn line(&mut self, from: (f32, f32), to: (f32, f32)) {
-        match self.render_queue.back_mut() {
-            Some(RenderTask {
-                shape: Shapes::Lines { coords },
-                render_parameter_state: color_state,
-                ..
-            }) if color_state.line_color
-                == self.render_parameter_state.line_color =>
-            {
-                coords.push(from);
-                coords.push(to);
-            }
-            _ => {
-                self.render_queue.push_back(RenderTask {
+    /// Add a vertex at position `pos` to the shape started with [`Window::begin_shape`].
+    /// The vertex takes the current fill color set with [`Window::fill_color`],
+    /// or for [`ShapeMode::Points`], [`ShapeMode::Lines`] and [`ShapeMode::LineStrip`],
+    /// the current line color set with [`Window::line_color`].
+    /// Changing the color between calls to `vertex` gives each vertex it's own color,
+    /// and the colors are blended across the shape.
+    pub fn vertex(&mut self, pos: (f32, f32)) {
+        self.vertex_uv(pos, (0., 0.));
+    }
+
+    /// Add a vertex at position `pos` with texture coordinates `uv` (in pixels)
+    /// to the shape started with [`Window::begin_shape`].
+    /// The texture is set with [`Window::shape_texture`].
+    /// See [`Window::vertex`] for details on the vertex color,
+    /// which is multiplied with the texture and the tint set with [`Window::tint`] at the time of [`Window::end_shape`].
+    pub fn vertex_uv(&mut self, pos: (f32, f32), uv: (f32, f32)) {
+        if let Some(mode) = self.shape_mode {
+            let color = match mode {
+                ShapeMode::Points | ShapeMode::Lines | ShapeMode::LineStrip => {
+                    self.render_parameter_state.line_color
+                }
+                _ => self.render_parameter_state.fill_color,
+            };
+            self.shape_vertices.push(ShapeVertex { pos, color, uv });
+        }
+    }
+
+    /// Set the texture of the shape started with [`Window::begin_shape`].
+    /// Which part of the texture is drawn is determined by the texture coordinates
+    /// passed to [`Window::vertex_uv`].
+    pub fn shape_texture(&mut self, texture: Option<Texture>) {
+        self.shape_texture = texture;
+    }
+
+    /// Finish the shape started with [`Window::begin_shape`] and draw it.
+    /// For [`ShapeMode::Polygon`], the outline of the shape is drawn with the
+    /// outline color and thickness set with [`Window::outline_color`] and [`Window::outline_thickness`],
+    /// outside of the shape like for all other shapes.
+    pub fn end_shape(&mut self) {
+        if let Some(mode) = self.shape_mode.take() {
+            if self.shape_vertices.is_empty() {
+                return;
+            }
+            self.render_queue.push_back(RenderTask {
+                pos: (0., 0.),
+                shape: Shapes::Custom {
+                    mode,
+                    vertices: std::mem::take(&mut self.shape_vertices),
+                    texture: self.shape_texture.take(),
+                },
+                render_parameter_state: self.render_parameter_state,
+                transform: self.transform,
+            })
+        }
+    }
+
+    /// Draw a texture `texture` at position `pos` with width and height of `(width, height)`.
+    /// The origin of the texture is at it's top left, unless changed with [`Window::image_mode`].
+    /// Textures can be loaded with [`Window::load_texture`].
+    /// # Examples
+    /// ```
+    /// let mut pg = Window::new_fullscreen();
+    /// let my_texture = pg.load_texture("my_texture.png").unwrap();
+    /// loop {
+    ///     pg.texture((100., 250.), my_texture, 100., 150.);
+    ///
+    ///     pg.update();
+    /// }
+    /// ```
+    pub fn texture(
+        &mut self,
+        pos: (f32, f32),
+        texture: Texture,
+        width: f32,
+        height: f32,
+    ) {
+        self.push_texture(pos, texture, texture.region(), width, height)
+    }
+
+    /// Draw a texture `texture` at position `pos` with width of `width`,
+    /// and height according to the aspect ratio of the texture.
+    /// The origin of the texture is at it's top left, unless changed with [`Window::image_mode`].
+    /// Textures can be loaded with [`Window::load_texture`].
+    /// # Examples
+    /// ```
+    /// let mut pg = Window::new_fullscreen();
+    /// let my_texture = pg.load_texture("my_texture.png").unwrap();
+    /// loop {
+    ///     pg.texture_((100., 250.), my_texture, 100.);
+    ///
+    ///     pg.update();
+    /// }
+    /// ```
+    pub fn texture_(&mut self, pos: (f32, f32), texture: Texture, width: f32) {
+        let height = width / texture.aspect();
+        self.push_texture(pos, texture, texture.region(), width, height)
+    }
+
+    /// Draw the part `src_rect` of texture `texture` at position `pos` with width and height of `(width, height)`.
+    /// `src_rect` is given as `(left, top, width, height)` in pixels of the texture.
+    /// The origin of the texture is at it's top left, unless changed with [`Window::image_mode`].
+    /// # Examples
+    /// ```
+    /// let mut pg = Window::new_fullscreen();
+    /// let atlas = pg.load_texture("atlas.png").unwrap();
+    /// loop {
+    ///     // Draw the 32x32 tile in the second column of the first row of the atlas
+    ///     pg.texture_region((100., 250.), atlas, (32, 0, 32, 32), 64., 64.);
+    ///
+    ///     pg.update();
+    /// }
+    /// ```
+    pub fn texture_region(
+        &mut self,
+        pos: (f32, f32),
+        texture: Texture,
+        src_rect: (u32, u32, u32, u32),
+        width: f32,
+        height: f32,
+    ) {
+        let (left, top, w, h) = src_rect;
+        let region = (left as i32, top as i32, w as i32, h as i32);
+        self.push_texture(pos, texture, region, width, height)
+    }
+
+    /// Draw the sprite `sprite`, like a frame of a [`SpriteSheet`],
+    /// at position `pos` with width and height of `(width, height)`.
+    /// The origin of the sprite is at it's top left, unless changed with [`Window::image_mode`].
+    /// # Examples
+    /// ```
+    /// let mut pg = Window::new_fullscreen();
+    /// let sheet = SpriteSheet::new(pg.load_texture("player.png").unwrap(), 8, 4);
+    /// loop {
+    ///     // Draw the first frame of the second row, facing left
+    ///     pg.sprite((100., 250.), sheet.frame(8).flip_horizontal(), 64., 64.);
+    ///
+    ///     pg.update();
+    /// }
+    /// ```
+    ///
+    /// [`SpriteSheet`]: crate::sprite::SpriteSheet
+    pub fn sprite(
+        &mut self,
+        pos: (f32, f32),
+        sprite: Sprite,
+        width: f32,
+        height: f32,
+    ) {
+        self.push_texture(pos, sprite.texture, sprite.region, width, height)
+    }
+
+    /// Draw the sprite `sprite` at position `pos` with width of `width`,
+    /// and height according to the aspect ratio of the sprite (See [`Window::sprite`]).
+    pub fn sprite_(&mut self, pos: (f32, f32), sprite: Sprite, width: f32) {
+        let height = width / sprite.aspect();
+        self.push_texture(pos, sprite.texture, sprite.region, width, height)
+    }
+
+    /// Draw the current frame of animation `animation` at position `pos` with width and height of `(width, height)`,
+    /// and advance it by the time since the last frame (See [`Window::deltatime`]).
+    /// An animation is only advanced once per frame, even if it is drawn multiple times.
+    /// The origin of the animation is at it's top left, unless changed with [`Window::image_mode`].
+    /// # Examples
+    /// ```
+    /// let mut pg = Window::new(800, 600, "Window Title");
+    /// let sheet = SpriteSheet::new(pg.load_texture("explosion.png").unwrap(), 4, 4);
+    /// let mut explosion = Animation::from_sprite_sheet(sheet, 0..16, 0.05, AnimationMode::Once);
+    /// loop {
+    ///     if !explosion.is_finished() {
+    ///         pg.animation((300., 200.), &mut explosion, 128., 128.);
+    ///     }
+    ///     pg.update();
+    /// }
+    /// ```
+    pub fn animation(
+        &mut self,
+        pos: (f32, f32),
+        animation: &mut Animation,
+        width: f32,
+        height: f32,
+    ) {
+        animation.advance_once(self.frame_count, self.deltatime);
+        if let Some(sprite) = animation.current() {
+            self.sprite(pos, sprite, width, height);
+        }
+    }
+
+    fn push_texture(
+        &mut self,
+        pos: (f32, f32),
+        texture: Texture,
+        region: (i32, i32, i32, i32),
+        width: f32,
+        height: f32,
+    ) {
+        self.render_queue.push_back(RenderTask {
+            pos,
+            shape: Shapes::Texture {
+                texture,
+                region,
+                width,
+                height,
+            },
+            render_parameter_state: self.render_parameter_state,
+            transform: self.transform,
+        })
+    }
+
+    /// Draw text `string` at position `pos`.
+    /// The text's top left is at `pos`, unless changed with [`Window::text_align`].
+    /// The default font size is 16 and can be changed with [`Window::font_size`].
+    /// The default font color is [`Color::BLACK`] and can be changed with [`Window::font_color`].
+    /// Uses the default font built into the library (Processing Sans Pro)
+    /// or the font set with [`Window::font`].
+    /// # Examples
+    /// ```
+    /// let mut pg = Window::new(720, 480, "Window Title");
+    /// loop {
+    ///     pg.fill_color(Color::BLACK);
+    ///     pg.text((10., 10.), "Hello World!");
+    ///     pg.update();
+    /// }
+    /// ```
+    pub fn text(&mut self, pos: (f32, f32), string: &str) {
+        self.render_queue.push_back(RenderTask {
+            pos,
+            shape: Shapes::Text {
+                string: string.to_string(),
+                font: self.font,
+            },
+            render_parameter_state: self.render_parameter_state,
+            transform: self.transform,
+        })
+    }
+
+    /// Draw text `string` inside a box at position `pos` with width and height of `(width, height)`.
+    /// The text is broken into lines between words so that it fits the width of the box,
+    /// as well as at every line break (`\n`) in `string`.
+    /// Lines that do not fit into the height of the box are left out,
+    /// or cut off with an ellipsis, depending on [`Window::text_overflow`].
+    ///
+    /// Each line is aligned inside the box according to [`Window::text_align`],
+    /// where [`VerticalAlign::Top`] and [`VerticalAlign::Baseline`] both put the
+    /// tallest letters of the first line at the top of the box.
+    /// The spacing between lines can be changed with [`Window::line_spacing`].
+    ///
+    /// # Examples
+    /// ```
+    /// let mut pg = Window::new(800, 600, "Window Title");
+    /// loop {
+    ///     pg.fill_color(Color::WHITE);
+    ///     pg.rectangle((100., 100.), 200., 80.);
+    ///     pg.text_overflow(TextOverflow::Ellipsis);
+    ///     pg.text_box((100., 100.), 200., 80., "A tooltip that is much too long to fit into it's box.");
+    ///     pg.update();
+    /// }
+    /// ```
+    pub fn text_box(
+        &mut self,
+        pos: (f32, f32),
+        width: f32,
+        height: f32,
+        string: &str,
+    ) {
+        let state = self.render_parameter_state;
+        let lines = self.with_text_format(|format| {
+            layout_box(
+                format,
+                string,
+                (width, height),
+                state.line_spacing,
+                state.text_overflow,
+                state.text_align,
+            )
+        });
+        if let Some(lines) = lines {
+            for (line, offset) in lines {
+                self.render_queue.push_back(RenderTask {
+                    pos: (pos.0 + offset.0, pos.1 + offset.1),
+                    shape: Shapes::Text {
+                        string: line,
+                        font: self.font,
+                    },
+                    render_parameter_state: RenderParameterState {
+                        text_align: (HorizontalAlign::Left, VerticalAlign::Top),
+                        ..state
+                    },
+                    transform: self.transform,
+                })
+            }
+        }
+    }
+
+    /// Draw rich text `rich_text`, made up of spans with different colors, sizes, fonts and styles,
+    /// at position `pos` (See [`RichText`]).
+    /// Anything not set by the spans is taken from the current font color, font size and font.
+    /// Each line is aligned according to [`Window::text_align`].
+    ///
+    /// # Examples
+    /// ```
+    /// let mut pg = Window::new(800, 600, "Window Title");
+    /// let text = RichText::parse("[b]Warning:[/b] [color=red]low battery[/color]");
+    /// loop {
+    ///     pg.rich_text((20., 20.), &text);
+    ///     pg.update();
+    /// }
+    /// ```
+    pub fn rich_text(&mut self, pos: (f32, f32), rich_text: &RichText) {
+        if self.is_headless() {
+            warn_not_drawn();
+            return;
+        }
+        let state = self.render_parameter_state;
+        let fragments = layout(
+            &self.assets.borrow(),
+            rich_text,
+            state.font_color,
+            state.font_size,
+            self.font,
+            state.text_align,
+        );
+        for fragment in fragments {
+            self.render_queue.push_back(RenderTask {
+                pos: (pos.0 + fragment.offset.0, pos.1 + fragment.offset.1),
+                shape: Shapes::Text {
+                    string: fragment.string,
+                    font: fragment.font,
+                },
+                render_parameter_state: RenderParameterState {
+                    font_color: fragment.color,
+                    font_size: fragment.size,
+                    text_style: fragment.style,
+                    text_align: (HorizontalAlign::Left, VerticalAlign::Top),
+                    ..state
+                },
+                transform: self.transform,
+            });
+            if let Some((x, y, width, height)) = fragment.strikethrough {
+                self.render_queue.push_back(RenderTask {
+                    pos: (pos.0 + x, pos.1 + y),
+                    shape: Shapes::Rectangle { width, height },
+                    render_parameter_state: RenderParameterState {
+                        fill_color: fragment.color,
+                        outline_color: Color::TRANSPARENT,
+                        rect_mode: DrawMode::Corner,
+                        ..state
+                    },
+                    transform: self.transform,
+                });
+            }
+        }
+    }
+
+    /// The width of text `string` if it were drawn with [`Window::text`],
+    /// using the current font, font size and text style.
+    /// For text with multiple lines, this is the width of the widest line.
+    pub fn text_width(&self, string: &str) -> f32 {
+        self.with_text_format(|f| text_width(f, string))
+            .unwrap_or(0.)
+    }
+
+    /// The bounding box `(left, top, width, height)` of text `string`
+    /// if it were drawn with [`Window::text`] at position `pos`,
+    /// using the current font, font size, text style and text alignment.
+    /// The box reaches from the ascent of the first line to the descent of the last line
+    /// (See [`Window::text_ascent`] and [`Window::text_descent`]),
+    /// and does not take transforms like [`Window::rotate`] into account.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut pg = Window::new(800, 600, "Window Title");
+    /// loop {
+    ///     // A label with a background
+    ///     let (x, y, w, h) = pg.text_bounds((100., 100.), "Label");
+    ///     pg.fill_color(Color::WHITE);
+    ///     pg.rectangle((x - 4., y - 4.), w + 8., h + 8.);
+    ///     pg.text((100., 100.), "Label");
+    ///     pg.update();
+    /// }
+    /// ```
+    pub fn text_bounds(
+        &self,
+        pos: (f32, f32),
+        string: &str,
+    ) -> (f32, f32, f32, f32) {
+        let align = self.render_parameter_state.text_align;
+        self.with_text_format(|f| {
+            let offset = align_offset(f, string, align);
+            (
+                pos.0 + offset.0,
+                pos.1 + offset.1 + f.size as f32 - ascent(f),
+                text_width(f, string),
+                text_height(f, string),
+            )
+        })
+        .unwrap_or((pos.0, pos.1, 0., 0.))
+    }
+
+    /// How far the tallest letters reach above the baseline,
+    /// using the current font and font size.
+    pub fn text_ascent(&self) -> f32 {
+        self.with_text_format(ascent).unwrap_or(0.)
+    }
+
+    /// How far the lowest letters (like "g" or "y") reach below the baseline,
+    /// using the current font and font size.
+    pub fn text_descent(&self) -> f32 {
+        self.with_text_format(descent).unwrap_or(0.)
+    }
+
+    /// Run `f` with the format text is currently drawn in, i.e. the current font, font size and text style.
+    /// Returns `None` if there is no font at all, or the window is headless,
+    /// since the glyphs of a font are kept on the graphics card.
+    fn with_text_format<R>(
+        &self,
+        f: impl FnOnce(&TextFormat) -> R,
+    ) -> Option<R> {
+        if self.is_headless() {
+            warn_not_drawn();
+            return None;
+        }
+        let assets = self.assets.borrow();
+        let font = assets.font_or_default(self.font)?;
+        Some(f(&TextFormat {
+            font,
+            size: self.render_parameter_state.font_size,
+            style: self.render_parameter_state.text_style,
+        }))
+    }
+
+    /// Draw a line from position `from` to position `to`.
+    /// The line's color is set with [`Window::line_color`].
+    pub fn line(&mut self, from: (f32, f32), to: (f32, f32)) {
+        match self.render_queue.back_mut() {
+            Some(RenderTask {
+                shape: Shapes::Lines { coords },
+                render_parameter_state: color_state,
+                transform,
+                ..
+            }) if color_state.line_color
+                == self.render_parameter_state.line_color
+                && color_state.line_width
+                    == self.render_parameter_state.line_width
+                && color_state.line_cap
+                    == self.render_parameter_state.line_cap
+                && color_state.target == self.render_parameter_state.target
+                && *transform == self.transform =>
+            {
+                coords.push(from);
+                coords.push(to);
+            }
+            _ => {
+                self.render_queue.push_back(RenderTask {
                     pos: (0., 0.),
                     shape: Shapes::Lines {
                         coords: vec![from, to],
                     },
                     render_parameter_state: self.render_parameter_state,
+                    transform: self.transform,
                 });
             }
         }
     }
 
+    /// Draw a line through all of `points`, in order.
+    /// Unlike drawing the individual segments with [`Window::line`],
+    /// the corners between segments of wide lines are joined
+    /// according to [`Window::line_join`].
+    pub fn polyline(&mut self, points: &[(f32, f32)]) {
+        if points.len() < 2 {
+            return;
+        }
+        self.render_queue.push_back(RenderTask {
+            pos: (0., 0.),
+            shape: Shapes::Polyline {
+                points: points.to_vec(),
+            },
+            render_parameter_state: self.render_parameter_state,
+            transform: self.transform,
+        })
+    }
+
+    /// Draw a cubic Bézier curve from `from` to `to`, with the control points `control1` and `control2`.
+    /// The curve is drawn with the line color and line width
+    /// set with [`Window::line_color`] and [`Window::line_width`].
+    /// Points and tangents on the curve can be calculated with [`bezier_point`] and [`bezier_tangent`].
+    ///
+    /// # Examples
+    /// ```
+    /// let mut pg = Window::new(800, 600, "Window Title");
+    /// loop {
+    ///     pg.line_width(3.);
+    ///     pg.bezier((100., 300.), (300., 100.), (500., 500.), (700., 300.));
+    ///     pg.update();
+    /// }
+    /// ```
+    ///
+    /// [`bezier_point`]: crate::bezier_point
+    /// [`bezier_tangent`]: crate::bezier_tangent
+    pub fn bezier(
+        &mut self,
+        from: (f32, f32),
+        control1: (f32, f32),
+        control2: (f32, f32),
+        to: (f32, f32),
+    ) {
+        let mut points = vec![from];
+        flatten_bezier(
+            &mut points,
+            from,
+            control1,
+            control2,
+            to,
+            self.curve_tolerance(),
+        );
+        self.polyline(&points);
+    }
+
+    /// Draw a quadratic Bézier curve from `from` to `to`, with the control point `control`.
+    /// The curve is drawn with the line color and line width
+    /// set with [`Window::line_color`] and [`Window::line_width`].
+    /// Points and tangents on the curve can be calculated with
+    /// [`quadratic_bezier_point`] and [`quadratic_bezier_tangent`].
+    ///
+    /// [`quadratic_bezier_point`]: crate::quadratic_bezier_point
+    /// [`quadratic_bezier_tangent`]: crate::quadratic_bezier_tangent
+    pub fn quadratic_bezier(
+        &mut self,
+        from: (f32, f32),
+        control: (f32, f32),
+        to: (f32, f32),
+    ) {
+        // A quadratic Bézier curve is a cubic one with both control points at 2/3 of the way to `control`.
+        let c1 = (
+            from.0 + 2. / 3. * (control.0 - from.0),
+            from.1 + 2. / 3. * (control.1 - from.1),
+        );
+        let c2 = (
+            to.0 + 2. / 3. * (control.0 - to.0),
+            to.1 + 2. / 3. * (control.1 - to.1),
+        );
+        self.bezier(from, c1, c2, to);
+    }
+
+    /// Draw a smooth curve (a Catmull-Rom spline) passing through all of `points`, in order.
+    /// The curve is drawn with the line color and line width
+    /// set with [`Window::line_color`] and [`Window::line_width`].
+    /// Points and tangents on the curve can be calculated with [`curve_point`] and [`curve_tangent`].
+    ///
+    /// # Examples
+    /// ```
+    /// let mut pg = Window::new(800, 600, "Window Title");
+    /// loop {
+    ///     pg.curve(&[(100., 300.), (250., 200.), (400., 350.), (550., 250.)]);
+    ///     pg.update();
+    /// }
+    /// ```
+    ///
+    /// [`curve_point`]: crate::curve_point
+    /// [`curve_tangent`]: crate::curve_tangent
+    pub fn curve(&mut self, points: &[(f32, f32)]) {
+        let mut flattened = Vec::new();
+        flatten_curve(&mut flattened, points, self.curve_tolerance());
+        self.polyline(&flattened);
+    }
+
+    /// How far the lines approximating a curve may deviate from the true curve,
+    /// such that the deviation stays below a quarter pixel on the screen.
+    fn curve_tolerance(&self) -> f32 {
+        0.25 / self.transform.scale_factor()
+    }
+
     /// Whether the keyboard key `key` is currently held pressed.
     /// # Examples
     /// ```
@@ -476,26 +1611,309 @@ impl Window<'_> {
 
     /// The width of the window, or the width of the screen in fullscreen mode.
     pub fn width(&self) -> f32 {
-        self.window.size().x as f32
+        self.surface.size().0 as f32
     }
 
     /// The height of the window, or the height of the screen in fullscreen mode.
     pub fn height(&self) -> f32 {
-        self.window.size().y as f32
+        self.surface.size().1 as f32
     }
 
     /// The time since the window has been created in seconds.
+    /// While recording, time passes at the recording's frame rate (See [`Window::start_recording`]).
     pub fn time(&self) -> f32 {
         self.runtime
     }
 
     /// How much time has passed since the last frame.
+    /// While recording, this is the time between two recorded frames (See [`Window::start_recording`]).
     pub fn deltatime(&self) -> f32 {
         self.deltatime
     }
 
+    /// The number of frames that have been completed with [`Window::update`] since the window has been created,
+    /// i.e. `0` in the first frame.
+    pub fn frame_count(&self) -> u64 {
+        self.frame_count
+    }
+
+    /// Read the current content of the window, i.e. everything drawn so far this frame,
+    /// into the window's pixels, which can then be read and changed with [`Window::get_pixel`],
+    /// [`Window::set_pixel`] or [`Window::pixels_mut`], and drawn back with [`Window::update_pixels`].
+    ///
+    /// # Examples
+    /// ```
+    /// let mut pg = Window::new(800, 600, "Window Title");
+    /// loop {
+    ///     pg.circle((400., 300.), 200.);
+    ///     // Invert the colors of the left half of the window
+    ///     pg.load_pixels();
+    ///     for y in 0..600 {
+    ///         for x in 0..400 {
+    ///             let c = pg.get_pixel(x, y);
+    ///             pg.set_pixel(x, y, Color::rgb(255 - c.red(), 255 - c.green(), 255 - c.blue()));
+    ///         }
+    ///     }
+    ///     pg.update_pixels();
+    ///     pg.update();
+    /// }
+    /// ```
+    pub fn load_pixels(&mut self) {
+        if let Some(image) = self.capture() {
+            self.pixels = image
+                .pixel_data()
+                .chunks_exact(4)
+                .map(|p| Color::rgba(p[0], p[1], p[2], p[3]))
+                .collect();
+        }
+        self.resize_pixels();
+    }
+
+    /// Draw the window's pixels (See [`Window::load_pixels`]) over everything drawn so far this frame.
+    /// Since drawing happens at the end of the frame, the pixels are drawn as they were
+    /// at the last call of [`Window::update_pixels`] in the frame.
+    pub fn update_pixels(&mut self) {
+        let Some(texture) = self.pixel_texture() else {
+            if self.is_headless() {
+                warn_not_drawn();
+            }
+            return;
+        };
+        self.resize_pixels();
+        let data: Vec<u8> = self
+            .pixels
+            .iter()
+            .flat_map(|c| [c.red(), c.green(), c.blue(), c.alpha()])
+            .collect();
+        if texture.update(&data).is_err() {
+            return;
+        }
+        self.render_queue.push_back(RenderTask {
+            pos: (0., 0.),
+            shape: Shapes::Texture {
+                texture,
+                region: texture.region(),
+                width: texture.width() as f32,
+                height: texture.height() as f32,
+            },
+            render_parameter_state: Default::default(),
+            transform: Transform::IDENTITY,
+        })
+    }
+
+    /// Capture the current content of the window, i.e. everything drawn so far this frame, as an image.
+    /// Returns `None` if the content of the window could not be read.
+    /// To save the whole frame to a file, [`Window::save_frame`] can be used instead.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut pg = Window::new(800, 600, "Window Title");
+    /// loop {
+    ///     pg.circle((400., 300.), 200.);
+    ///     if pg.key_just_pressed(Key::C) {
+    ///         let image = pg.capture().unwrap();
+    ///         println!("{:?}", image.pixel_at(400, 300));
+    ///     }
+    ///     pg.update();
+    /// }
+    /// ```
+    pub fn capture(&mut self) -> Option<Image> {
+        self.draw_queue();
+        self.window_image()
+    }
+
+    /// Save the frame to the image file at `path`, once it has been drawn completely in [`Window::update`].
+    /// A run of `#` in `path` is replaced by the frame number (See [`Window::frame_count`]),
+    /// padded with leading zeros to the length of the run, like for Processing's `saveFrame`,
+    /// e.g. `"frame-####.png"` is saved as `"frame-0042.png"` in frame `42`.
+    /// The file type is chosen by the file extension, one of `png`, `jpg`, `bmp` or `tga`.
+    /// Returns an error if the file extension is not supported.
+    /// If the file can not be written at the end of the frame, the error is printed.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut pg = Window::new(800, 600, "Window Title");
+    /// loop {
+    ///     pg.circle(pg.mouse_position(), 50.);
+    ///     if pg.key_just_pressed(Key::S) {
+    ///         pg.save_frame("screenshot-####.png").unwrap();
+    ///     }
+    ///     pg.update();
+    /// }
+    /// ```
+    pub fn save_frame(&mut self, path: &str) -> Result<(), Error> {
+        let path = frame_file_name(path, self.frame_count);
+        check_image_save_extension(&path)?;
+        self.save_frame_paths.push(path);
+        Ok(())
+    }
+
+    /// Start recording the frames of the window to `path` at `fps` frames per second,
+    /// as an animated GIF if `path` ends in `.gif`,
+    /// and as a sequence of numbered image files otherwise (See [`Window::save_frame`] for the file types and numbering).
+    /// The frames are numbered from `0`, and if `path` does not contain a run of `#`,
+    /// the frame number is added before the file extension, e.g. `"frame.png"` is recorded as `"frame-00000.png"`, ....
+    ///
+    /// While recording, time passes at the recording's frame rate instead of in real time,
+    /// i.e. [`Window::deltatime`] is `1/fps`, so that animations based on it play back at the intended speed,
+    /// no matter how long each frame actually takes to draw and save.
+    /// GIFs can not play faster than 100 frames per second,
+    /// so GIFs recorded at a higher frame rate play back slower than intended.
+    /// A recording that is already running is stopped first.
+    /// Returns an error if `fps` is not positive, the file type is not supported or the GIF file can not be created.
+    /// Failing to save a frame during the recording prints the error and stops the recording.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut pg = Window::new(400, 400, "Window Title");
+    /// pg.start_recording("spinner.gif", 30.).unwrap();
+    /// loop {
+    ///     pg.translate((200., 200.));
+    ///     pg.rotate(pg.time());
+    ///     pg.rectangle((-50., -10.), 100., 20.);
+    ///     if pg.time() > 5. {
+    ///         pg.stop_recording().unwrap(); // Records five seconds of spinning
+    ///     }
+    ///     pg.update();
+    /// }
+    /// ```
+    pub fn start_recording(
+        &mut self,
+        path: &str,
+        fps: f32,
+    ) -> Result<(), Error> {
+        self.stop_recording()?;
+        let (width, height) = self.surface.size();
+        self.recording = Some(Recording::new(path, fps, width, height)?);
+        Ok(())
+    }
+
+    /// Stop the recording started with [`Window::start_recording`], finishing the GIF file if recording to one.
+    /// Afterwards, time passes in real time again.
+    /// Does nothing if there is no recording.
+    pub fn stop_recording(&mut self) -> Result<(), Error> {
+        match self.recording.take() {
+            Some(recording) => recording.finish(),
+            None => Ok(()),
+        }
+    }
+
+    /// Whether frames are currently being recorded (See [`Window::start_recording`]).
+    pub fn is_recording(&self) -> bool {
+        self.recording.is_some()
+    }
+
+    /// Set a key that starts and stops recording to `path` at `fps` frames per second when pressed
+    /// (See [`Window::start_recording`]).
+    /// Errors when starting or stopping the recording are printed.
+    /// A value of `None` for `key` removes the hotkey.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut pg = Window::new(800, 600, "Window Title");
+    /// pg.recording_hotkey(Some(Key::R), "recording-####.png", 60.);
+    /// loop {
+    ///     pg.circle(pg.mouse_position(), 20.);
+    ///     pg.update();
+    /// }
+    /// ```
+    pub fn recording_hotkey(&mut self, key: Option<Key>, path: &str, fps: f32) {
+        self.recording_hotkey = key.map(|key| (key, path.to_string(), fps));
+    }
+
+    /// Whether the window is headless (See [`Window::new_headless`]).
+    fn is_headless(&self) -> bool {
+        matches!(self.surface, Surface::Headless { .. })
+    }
+
+    /// The content of the window drawn so far, without drawing the render queue first.
+    fn window_image(&mut self) -> Option<Image> {
+        if let Surface::Headless { frame, .. } = &self.surface {
+            let data: Vec<u8> = frame
+                .pixels()
+                .iter()
+                .flat_map(|c| [c.red(), c.green(), c.blue(), c.alpha()])
+                .collect();
+            return Image::create_from_pixels(
+                frame.width(),
+                frame.height(),
+                &data,
+            );
+        }
+        let texture = self.pixel_texture()?;
+        let Surface::Screen(window) = &self.surface else {
+            return None;
+        };
+        let mut assets = self.assets.borrow_mut();
+        let t = assets.texture_mut(texture)?;
+        t.update_from_render_window(window, 0, 0);
+        t.copy_to_image()
+    }
+
+    /// The window's pixels (See [`Window::load_pixels`]), row by row from the top left.
+    /// The pixel at `(x, y)` is at index `y * width + x`.
+    /// Before [`Window::load_pixels`] has been called, all pixels are transparent.
+    pub fn pixels(&self) -> &[Color] {
+        &self.pixels
+    }
+
+    /// The window's pixels (See [`Window::pixels`]), to be changed directly.
+    pub fn pixels_mut(&mut self) -> &mut [Color] {
+        self.resize_pixels();
+        &mut self.pixels
+    }
+
+    /// Make sure there is a pixel in [`Window::pixels`] for every pixel of the window.
+    fn resize_pixels(&mut self) {
+        let (width, height) = self.surface.size();
+        self.pixels
+            .resize(width as usize * height as usize, Color::TRANSPARENT);
+        self.pixels_width = width;
+    }
+
+    /// The color of the window's pixel at `(x, y)` (See [`Window::load_pixels`]).
+    /// Pixels outside of the window are transparent.
+    pub fn get_pixel(&self, x: u32, y: u32) -> Color {
+        self.pixel_index(x, y)
+            .and_then(|i| self.pixels.get(i))
+            .copied()
+            .unwrap_or(Color::TRANSPARENT)
+    }
+
+    /// Set the color of the window's pixel at `(x, y)` to `color` (See [`Window::load_pixels`]).
+    /// The change only appears on the screen after [`Window::update_pixels`].
+    pub fn set_pixel<C: Into<Color>>(&mut self, x: u32, y: u32, color: C) {
+        if self.pixels.is_empty() {
+            self.resize_pixels();
+        }
+        if let Some(i) = self.pixel_index(x, y) {
+            self.pixels[i] = color.into();
+        }
+    }
+
+    /// The index of the pixel at `(x, y)` in [`Window::pixels`], if it is inside the window.
+    /// Uses the width the pixels were last sized for, so that no window size has to be queried per pixel.
+    fn pixel_index(&self, x: u32, y: u32) -> Option<usize> {
+        let i = y as usize * self.pixels_width as usize + x as usize;
+        (x < self.pixels_width && i < self.pixels.len()).then_some(i)
+    }
+
+    /// The window-sized texture for reading and drawing the window's pixels.
+    fn pixel_texture(&mut self) -> Option<Texture> {
+        if self.is_headless() {
+            return None;
+        }
+        let mut assets = self.assets.borrow_mut();
+        if self.pixel_texture.and_then(|t| assets.texture(t)).is_none() {
+            let (width, height) = self.surface.size();
+            self.pixel_texture =
+                Some(assets.add_texture(SfmlTexture::new(width, height)?));
+        }
+        self.pixel_texture
+    }
+
     /// Load a texture from path `path`.
-    /// A return value of `None` means that the texture could not be loaded.
+    /// If the texture could not be loaded, the returned [`Error`] tells why.
     /// On success, returns a [`Texture`] object that can be passed to the [`Window::texture`] function to draw the texture to the screen.
     ///
     /// # Examples
@@ -508,12 +1926,79 @@ impl Window<'_> {
     ///     pg.update();
     /// }
     /// ```
-    pub fn load_texture(&mut self, path: &str) -> Option<Texture> {
-        texture_store_add(SfmlTexture::from_file(path)?)
+    pub fn load_texture(&mut self, path: &str) -> Result<Texture, Error> {
+        if self.is_headless() {
+            return Err(Error::Headless);
+        }
+        let texture = self
+            .assets
+            .borrow_mut()
+            .add_texture(texture_from_file(path)?);
+        if self.hot_reload {
+            self.watched_files
+                .push(WatchedFile::new(path, Asset::Texture(texture)));
+        }
+        Ok(texture)
+    }
+
+    /// Create a texture of width `width` and height `height` from the pixel data `pixels`,
+    /// which holds four bytes (red, green, blue, alpha) per pixel, row by row.
+    /// Returns an error if `pixels` does not have the size of the texture,
+    /// or if the texture could not be created.
+    /// The pixels of the texture can be changed later with [`Texture::update`].
+    ///
+    /// # Examples
+    /// ```
+    /// let mut pg = Window::new(800, 600, "Window Title");
+    /// // A red to green gradient
+    /// let pixels: Vec<u8> = (0..256 * 16)
+    ///     .flat_map(|i| [(i % 256) as u8, 255 - (i % 256) as u8, 0, 255])
+    ///     .collect();
+    /// let gradient = pg.create_texture(256, 16, &pixels).unwrap();
+    /// loop {
+    ///     pg.texture((0., 0.), gradient, 800., 50.);
+    ///     pg.update();
+    /// }
+    /// ```
+    pub fn create_texture(
+        &mut self,
+        width: u32,
+        height: u32,
+        pixels: &[u8],
+    ) -> Result<Texture, Error> {
+        if self.is_headless() {
+            return Err(Error::Headless);
+        }
+        check_pixel_data(pixels, width, height)?;
+        let texture = SfmlTexture::new(width, height)
+            .ok_or(Error::InvalidSize { width, height })?;
+        let texture = self.assets.borrow_mut().add_texture(texture);
+        texture.update(pixels)?;
+        Ok(texture)
+    }
+
+    /// Create a canvas of width `width` and height `height`, that can be drawn to like the window
+    /// after passing it to [`Window::canvas`], and drawn like a texture with [`Canvas::texture`].
+    /// The canvas starts out transparent.
+    /// Returns an error if the canvas could not be created, e.g. because it is too large,
+    /// or because the window is headless (See [`Window::new_headless`]).
+    pub fn create_canvas(
+        &mut self,
+        width: u32,
+        height: u32,
+    ) -> Result<Canvas, Error> {
+        if self.is_headless() {
+            return Err(Error::Headless);
+        }
+        let mut render_texture = RenderTexture::new(width, height, false)
+            .ok_or(Error::InvalidSize { width, height })?;
+        render_texture.clear(Color::TRANSPARENT.into());
+        render_texture.display();
+        Ok(self.assets.borrow_mut().add_canvas(render_texture))
     }
 
     /// Load a font from path `path`.
-    /// A return value of `None` means that the font could not be loaded.
+    /// If the font could not be loaded, the returned [`Error`] tells why.
     /// On success, returns a [`Font`] object that can be passed to the [`Window::font`] function
     /// to set the font to be used for drawing text with [`Window::text`].
     ///
@@ -528,7 +2013,594 @@ impl Window<'_> {
     ///     pg.update();
     /// }
     /// ```
-    pub fn load_font(&mut self, path: &str) -> Option<Font> {
-        font_store_add(SfmlFont::from_file(path)?)
+    pub fn load_font(&mut self, path: &str) -> Result<Font, Error> {
+        let font = StoredFont::from_file(path)?;
+        let font = self.assets.borrow_mut().add_font(font);
+        if self.hot_reload {
+            self.watched_files
+                .push(WatchedFile::new(path, Asset::Font(font)));
+        }
+        Ok(font)
+    }
+
+    /// Load a texture from the contents of an image file `bytes`, like a PNG or JPEG file
+    /// included in the program with [`include_bytes!`].
+    /// If the texture could not be loaded, the returned [`Error`] tells why.
+    /// See [`Window::load_texture`].
+    ///
+    /// # Examples
+    /// ```
+    /// let mut pg = Window::new_fullscreen();
+    /// let my_texture = pg
+    ///     .load_texture_from_bytes(include_bytes!("my_texture.png"))
+    ///     .unwrap();
+    /// loop {
+    ///     pg.texture_((100., 250.), my_texture, 100.);
+    ///
+    ///     pg.update();
+    /// }
+    /// ```
+    pub fn load_texture_from_bytes(
+        &mut self,
+        bytes: &[u8],
+    ) -> Result<Texture, Error> {
+        if self.is_headless() {
+            return Err(Error::Headless);
+        }
+        let texture = SfmlTexture::from_memory(bytes, &IntRect::default())
+            .ok_or_else(|| Error::Decode(String::from("texture data")))?;
+        Ok(self.assets.borrow_mut().add_texture(texture))
+    }
+
+    /// Load a font from the contents of a font file `bytes`, like a TTF file
+    /// included in the program with [`include_bytes!`].
+    /// If the font could not be loaded, the returned [`Error`] tells why.
+    /// See [`Window::load_font`].
+    ///
+    /// # Examples
+    /// ```
+    /// let mut pg = Window::new_fullscreen();
+    /// let my_font = pg.load_font_from_bytes(include_bytes!("MyFont.ttf")).unwrap();
+    /// pg.font(Some(my_font));
+    /// loop {
+    ///     pg.text((20, 20), "This text is drawn in MyFont.");
+    ///
+    ///     pg.update();
+    /// }
+    /// ```
+    pub fn load_font_from_bytes(
+        &mut self,
+        bytes: &[u8],
+    ) -> Result<Font, Error> {
+        let font = StoredFont::from_memory(bytes)?;
+        Ok(self.assets.borrow_mut().add_font(font))
+    }
+
+    /// Load a texture from the file at path `path` inside the asset folder `A`,
+    /// embedded into the program with [`RustEmbed`].
+    /// If there is no such file, or the texture could not be loaded,
+    /// the returned [`Error`] tells why.
+    ///
+    /// # Examples
+    /// ```
+    /// use pronto_graphics::{rust_embed, RustEmbed};
+    ///
+    /// #[derive(RustEmbed)]
+    /// #[folder = "res/"]
+    /// struct Assets;
+    ///
+    /// let mut pg = Window::new_fullscreen();
+    /// let my_texture = pg.load_texture_from_asset::<Assets>("my_texture.png").unwrap();
+    /// loop {
+    ///     pg.texture_((100., 250.), my_texture, 100.);
+    ///
+    ///     pg.update();
+    /// }
+    /// ```
+    pub fn load_texture_from_asset<A: RustEmbed>(
+        &mut self,
+        path: &str,
+    ) -> Result<Texture, Error> {
+        check_texture_extension(path)?;
+        let file = A::get(path)
+            .ok_or_else(|| Error::FileNotFound(path.to_string()))?;
+        self.load_texture_from_bytes(&file.data)
+    }
+
+    /// Load a font from the file at path `path` inside the asset folder `A`,
+    /// embedded into the program with [`RustEmbed`].
+    /// If there is no such file, or the font could not be loaded,
+    /// the returned [`Error`] tells why.
+    ///
+    /// # Examples
+    /// ```
+    /// use pronto_graphics::{rust_embed, RustEmbed};
+    ///
+    /// #[derive(RustEmbed)]
+    /// #[folder = "res/"]
+    /// struct Assets;
+    ///
+    /// let mut pg = Window::new_fullscreen();
+    /// let my_font = pg.load_font_from_asset::<Assets>("fonts/MyFont.ttf").unwrap();
+    /// pg.font(Some(my_font));
+    /// loop {
+    ///     pg.text((20, 20), "This text is drawn in MyFont.");
+    ///
+    ///     pg.update();
+    /// }
+    /// ```
+    pub fn load_font_from_asset<A: RustEmbed>(
+        &mut self,
+        path: &str,
+    ) -> Result<Font, Error> {
+        check_font_extension(path)?;
+        let file = A::get(path)
+            .ok_or_else(|| Error::FileNotFound(path.to_string()))?;
+        self.load_font_from_bytes(&file.data)
+    }
+
+    /// Enable or disable hot reloading, for quickly iterating on textures and fonts during development.
+    /// While enabled, the files of all textures and fonts loaded with [`Window::load_texture`]
+    /// and [`Window::load_font`] since it was enabled are checked for changes in every [`Window::update`],
+    /// and changed files are loaded again, such that the existing [`Texture`] and [`Font`] objects
+    /// show the new content.
+    /// If a changed file can not be loaded, the old content is kept and the error is printed.
+    /// Hot reloading has to be enabled before loading the textures and fonts to watch,
+    /// and disabling it stops watching all of them.
+    /// Hot reloading is disabled by default.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut pg = Window::new(800, 600, "Window Title");
+    /// pg.hot_reload(cfg!(debug_assertions));
+    /// let sprite = pg.load_texture("sprite.png").unwrap();
+    /// loop {
+    ///     // Editing and saving sprite.png changes what is drawn here
+    ///     pg.texture((100., 100.), sprite, 200., 200.);
+    ///     pg.update();
+    /// }
+    /// ```
+    pub fn hot_reload(&mut self, enabled: bool) {
+        self.hot_reload = enabled;
+        if !enabled {
+            self.watched_files.clear();
+        }
+    }
+
+    /// Why the default font, which is stored inside the library, could not be loaded,
+    /// or `None` if it was loaded just fine.
+    /// Without the default font, no text is drawn unless another font is set with [`Window::font`].
+    pub fn default_font_error(&self) -> Option<Error> {
+        self.assets.borrow().default_font_error()
+    }
+
+    /// Unload texture `texture`, freeing it's memory.
+    /// Afterwards, `texture` is no longer valid, and drawing it does nothing,
+    /// even if another texture is loaded later.
+    /// Returns `false` if the texture had already been unloaded.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut pg = Window::new_fullscreen();
+    /// let mut photo = pg.load_texture("photo_0.png").unwrap();
+    /// let mut i = 0;
+    /// loop {
+    ///     if pg.key_just_pressed(Key::SPACE) {
+    ///         // Replace the photo with the next one, without keeping the old one in memory
+    ///         pg.unload_texture(photo);
+    ///         i += 1;
+    ///         photo = pg.load_texture(&format!("photo_{}.png", i)).unwrap();
+    ///     }
+    ///     pg.texture_((0., 0.), photo, 800.);
+    ///
+    ///     pg.update();
+    /// }
+    /// ```
+    pub fn unload_texture(&mut self, texture: Texture) -> bool {
+        self.watched_files
+            .retain(|f| f.asset() != Asset::Texture(texture));
+        self.assets.borrow_mut().remove_texture(texture)
+    }
+
+    /// Unload font `font`, freeing it's memory.
+    /// Afterwards, `font` is no longer valid, and text that would be drawn in it
+    /// is drawn in the default font instead, even if another font is loaded later.
+    /// Returns `false` if the font had already been unloaded.
+    pub fn unload_font(&mut self, font: Font) -> bool {
+        if self.font == Some(font) {
+            self.font = None;
+        }
+        self.watched_files
+            .retain(|f| f.asset() != Asset::Font(font));
+        self.assets.borrow_mut().remove_font(font)
+    }
+}
+
+/// What a window draws to.
+enum Surface {
+    /// A window on the screen, drawn to by SFML.
+    Screen(RenderWindow),
+    /// An image in memory, drawn to in software (See [`Window::new_headless`]).
+    /// With persistent drawing, the drawings are kept on the `persistent` layer,
+    /// which is drawn over the background into the `frame`.
+    Headless {
+        frame: Raster,
+        persistent: Option<Raster>,
+    },
+}
+
+impl Surface {
+    fn size(&self) -> (u32, u32) {
+        match self {
+            Surface::Screen(window) => (window.size().x, window.size().y),
+            Surface::Headless { frame, .. } => (frame.width(), frame.height()),
+        }
+    }
+
+    fn clear(&mut self, color: Color) {
+        match self {
+            Surface::Screen(window) => window.clear(color.into()),
+            Surface::Headless { frame, .. } => frame.clear(color),
+        }
+    }
+}
+
+/// Draw the render task `task` to `target`.
+fn draw_task(
+    target: &mut dyn RenderTarget,
+    shape_store: &mut ShapeStore,
+    assets: &Assets,
+    task: &RenderTask,
+) {
+    let RenderTask {
+        pos,
+        shape,
+        render_parameter_state: color_state,
+        transform,
+    } = task;
+
+    let mut states = RenderStates::default();
+    states.set_transform((*transform).into());
+
+    match shape {
+        Shapes::Circle { radius } => {
+            let (center, (radius_x, radius_y)) =
+                color_state.ellipse_mode.ellipse(*pos, (*radius, *radius));
+            if radius_x != radius_y {
+                // Only possible with `DrawMode::Corners`
+                let points = ellipse_points(
+                    center,
+                    (radius_x, radius_y),
+                    transform.scale_factor(),
+                );
+                draw_filled_shape(target, &points, color_state, &states);
+                return;
+            }
+            let s = &mut shape_store.circle;
+            s.set_radius(radius_x);
+            s.set_point_count(circle_segments(
+                radius_x * transform.scale_factor(),
+            ) as u32);
+            s.set_origin((s.radius(), s.radius()));
+            s.set_position(center);
+            s.set_fill_color(color_state.fill_color.into());
+            s.set_outline_color(color_state.outline_color.into());
+            s.set_outline_thickness(color_state.outline_thickness);
+            target.draw_with_renderstates(s, &states);
+        }
+        Shapes::Rectangle { width, height } => {
+            let (top_left, size) =
+                color_state.rect_mode.rectangle(*pos, (*width, *height));
+            let s = &mut shape_store.rectangle;
+            s.set_size(size);
+            s.set_position(top_left);
+            s.set_fill_color(color_state.fill_color.into());
+            s.set_outline_color(color_state.outline_color.into());
+            s.set_outline_thickness(color_state.outline_thickness);
+            target.draw_with_renderstates(s, &states);
+        }
+        Shapes::Ellipse { radius_x, radius_y } => {
+            let (center, radius) = color_state
+                .ellipse_mode
+                .ellipse(*pos, (*radius_x, *radius_y));
+            let points =
+                ellipse_points(center, radius, transform.scale_factor());
+            draw_filled_shape(target, &points, color_state, &states);
+        }
+        Shapes::Arc { radius, start, end } => {
+            let scale = transform.scale_factor();
+            let (center, (radius_x, radius_y)) =
+                color_state.ellipse_mode.ellipse(*pos, (*radius, *radius));
+            let mut points =
+                arc_points(center, (radius_x, radius_y), *start, *end, scale);
+            match color_state.arc_mode {
+                ArcMode::Open => {
+                    let fill = RenderParameterState {
+                        outline_thickness: 0.,
+                        ..*color_state
+                    };
+                    draw_filled_shape(target, &points, &fill, &states);
+
+                    // Stroke along a slightly larger arc,
+                    // so that the outline lies outside the arc.
+                    let t = color_state.outline_thickness;
+                    let r = (radius_x + t / 2., radius_y + t / 2.);
+                    let points = arc_points(center, r, *start, *end, scale);
+                    let triangles = stroke(
+                        &points,
+                        false,
+                        t,
+                        LineCap::Butt,
+                        LineJoin::Miter,
+                    );
+                    let va = triangles_vertex_array(
+                        &triangles,
+                        color_state.outline_color,
+                    );
+                    target.draw_with_renderstates(&va, &states);
+                }
+                ArcMode::Chord => {
+                    draw_filled_shape(target, &points, color_state, &states)
+                }
+                ArcMode::Pie => {
+                    points.push(center);
+                    draw_filled_shape(target, &points, color_state, &states);
+                }
+            }
+        }
+        Shapes::RoundedRectangle {
+            width,
+            height,
+            corner_radius,
+        } => {
+            let (top_left, (width, height)) =
+                color_state.rect_mode.rectangle(*pos, (*width, *height));
+            let points = rounded_rectangle_points(
+                top_left,
+                width,
+                height,
+                *corner_radius,
+                transform.scale_factor(),
+            );
+            draw_filled_shape(target, &points, color_state, &states);
+        }
+        Shapes::Lines { coords } => {
+            let va = if color_state.line_width <= 1. {
+                let mut va =
+                    VertexArray::new(PrimitiveType::LINES, coords.len());
+                for (i, v) in coords.iter().enumerate() {
+                    va[i] = Vertex::with_pos_color(
+                        (*v).into(),
+                        color_state.line_color.into(),
+                    );
+                }
+                va
+            } else {
+                let triangles: Vec<_> = coords
+                    .chunks_exact(2)
+                    .flat_map(|segment| {
+                        stroke(
+                            segment,
+                            false,
+                            color_state.line_width,
+                            color_state.line_cap,
+                            color_state.line_join,
+                        )
+                    })
+                    .collect();
+                triangles_vertex_array(&triangles, color_state.line_color)
+            };
+
+            target.draw_with_renderstates(&va, &states);
+        }
+        Shapes::Polygon { points } => {
+            draw_filled_shape(target, points, color_state, &states);
+        }
+        Shapes::Polyline { points } => {
+            let va = line_vertex_array(
+                points,
+                false,
+                color_state.line_width,
+                color_state.line_color,
+                color_state,
+            );
+            target.draw_with_renderstates(&va, &states);
+        }
+        Shapes::Custom {
+            mode,
+            vertices,
+            texture,
+        } => {
+            let mut fill_states = states;
+            let tex = texture.and_then(|t| assets.texture(t));
+            fill_states.set_texture(tex);
+            // Like textures, textured shapes are tinted
+            let tint = match tex {
+                Some(_) => color_state.tint,
+                None => Color::WHITE,
+            };
+            let vertex = |v: &ShapeVertex| {
+                Vertex::new(v.pos.into(), (v.color * tint).into(), v.uv.into())
+            };
+
+            let (primitive_type, indices) = match mode {
+                ShapeMode::Points => (PrimitiveType::POINTS, None),
+                ShapeMode::Lines => (PrimitiveType::LINES, None),
+                ShapeMode::LineStrip => (PrimitiveType::LINE_STRIP, None),
+                ShapeMode::Triangles => (PrimitiveType::TRIANGLES, None),
+                ShapeMode::TriangleFan => (PrimitiveType::TRIANGLE_FAN, None),
+                ShapeMode::TriangleStrip => {
+                    (PrimitiveType::TRIANGLE_STRIP, None)
+                }
+                ShapeMode::Polygon => {
+                    let points: Vec<_> =
+                        vertices.iter().map(|v| v.pos).collect();
+                    (PrimitiveType::TRIANGLES, Some(triangulate(&points)))
+                }
+            };
+
+            let mut va = VertexArray::new(primitive_type, 0);
+            match indices {
+                Some(indices) => indices
+                    .iter()
+                    .for_each(|i| va.append(&vertex(&vertices[*i]))),
+                None => vertices.iter().for_each(|v| va.append(&vertex(v))),
+            }
+            target.draw_with_renderstates(&va, &fill_states);
+
+            if *mode == ShapeMode::Polygon {
+                let points: Vec<_> = vertices.iter().map(|v| v.pos).collect();
+                let triangles = outline(&points, color_state.outline_thickness);
+                let va = triangles_vertex_array(
+                    &triangles,
+                    color_state.outline_color,
+                );
+                target.draw_with_renderstates(&va, &states);
+            }
+        }
+        Shapes::Texture {
+            texture,
+            region,
+            width,
+            height,
+        } => {
+            if let Some(tex) = assets.texture(*texture) {
+                let ((x, y), (w, h)) =
+                    color_state.image_mode.rectangle(*pos, (*width, *height));
+                let (left, top, region_w, region_h) = *region;
+                let (offset, scale) =
+                    (color_state.texture_offset, color_state.texture_scale);
+                let uv = |u: f32, v: f32| {
+                    (
+                        left as f32 + offset.0 + u * region_w as f32 * scale.0,
+                        top as f32 + offset.1 + v * region_h as f32 * scale.1,
+                    )
+                };
+                let mut va = VertexArray::new(PrimitiveType::TRIANGLE_STRIP, 4);
+                for (i, (u, v)) in [(0., 0.), (1., 0.), (0., 1.), (1., 1.)]
+                    .into_iter()
+                    .enumerate()
+                {
+                    va[i] = Vertex::new(
+                        (x + u * w, y + v * h).into(),
+                        color_state.tint.into(),
+                        uv(u, v).into(),
+                    );
+                }
+                let mut texture_states = states;
+                texture_states.set_texture(Some(tex));
+                target.draw_with_renderstates(&va, &texture_states);
+            }
+        }
+        Shapes::Clear { color } => target.clear((*color).into()),
+        Shapes::Text { string, font } => {
+            // If no kind of font was found, we just don't show any text
+            if let Some(sfml_font) = assets.font_or_default(*font) {
+                let format = TextFormat {
+                    font: sfml_font,
+                    size: color_state.font_size,
+                    style: color_state.text_style,
+                };
+                let lines =
+                    align_lines(&format, string, color_state.text_align);
+                for (line, offset) in lines {
+                    let mut t = format.text(line);
+                    t.set_fill_color(color_state.font_color.into());
+                    t.set_position((pos.0 + offset.0, pos.1 + offset.1));
+                    target.draw_with_renderstates(&t, &states);
+                }
+            }
+        }
+    }
+}
+
+/// Build a vertex array filled with the triangles `triangles` in color `color`.
+fn triangles_vertex_array(
+    triangles: &[(f32, f32)],
+    color: Color,
+) -> VertexArray {
+    let mut va = VertexArray::new(PrimitiveType::TRIANGLES, triangles.len());
+    for (i, v) in triangles.iter().enumerate() {
+        va[i] = Vertex::with_pos_color((*v).into(), color.into());
+    }
+    va
+}
+
+/// Build a vertex array for a line through `points` of width `width` and color `color`,
+/// with the line cap and join from `state`.
+/// Lines with a width of up to `1` are drawn as hairlines.
+fn line_vertex_array(
+    points: &[(f32, f32)],
+    closed: bool,
+    width: f32,
+    color: Color,
+    state: &RenderParameterState,
+) -> VertexArray {
+    if width <= 0. {
+        VertexArray::default()
+    } else if width <= 1. {
+        let mut va = VertexArray::new(PrimitiveType::LINE_STRIP, 0);
+        let end = if closed { points.get(..1) } else { None };
+        for v in points.iter().chain(end.into_iter().flatten()) {
+            va.append(&Vertex::with_pos_color((*v).into(), color.into()));
+        }
+        va
+    } else {
+        let triangles =
+            stroke(points, closed, width, state.line_cap, state.line_join);
+        triangles_vertex_array(&triangles, color)
+    }
+}
+
+/// Draw the polygon `points` filled with the fill color and outlined with the outline color of `state`.
+/// Like for SFML's shapes, the outline lies outside of the polygon.
+fn draw_filled_shape(
+    target: &mut dyn RenderTarget,
+    points: &[(f32, f32)],
+    state: &RenderParameterState,
+    states: &RenderStates,
+) {
+    let triangles: Vec<_> =
+        triangulate(points).iter().map(|i| points[*i]).collect();
+    let va = triangles_vertex_array(&triangles, state.fill_color);
+    target.draw_with_renderstates(&va, states);
+
+    let triangles = outline(points, state.outline_thickness);
+    let va = triangles_vertex_array(&triangles, state.outline_color);
+    target.draw_with_renderstates(&va, states);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn headless_without_display() {
+        // Anything that needs a graphics context would fail to open a display here
+        std::env::remove_var("DISPLAY");
+        let mut pg = Window::new_headless(64, 32);
+
+        pg.text((0., 0.), "Skipped");
+        pg.text_box((0., 0.), 64., 32., "Skipped");
+        pg.rich_text((0., 0.), &RichText::parse("[b]Skipped[/b]"));
+        assert_eq!(pg.text_width("Skipped"), 0.);
+        assert_eq!(pg.text_bounds((1., 2.), "Skipped"), (1., 2., 0., 0.));
+        assert_eq!(pg.text_ascent(), 0.);
+        assert_eq!(pg.text_descent(), 0.);
+
+        assert!(pg.load_texture("missing.png") == Err(Error::Headless));
+        assert!(pg.create_texture(1, 1, &[0; 4]) == Err(Error::Headless));
+        assert!(pg.load_texture_from_bytes(&[]) == Err(Error::Headless));
+        assert!(pg.create_canvas(8, 8) == Err(Error::Headless));
+
+        pg.background_color(Color::BLUE);
+        pg.fill_color(Color::RED);
+        pg.rectangle((0., 0.), 16., 32.);
+        pg.update_pixels();
+        let image = pg.capture().unwrap();
+        assert_eq!(image.pixel_at(0, 0), Color::RED.into());
+        assert_eq!(image.pixel_at(63, 0), Color::BLUE.into());
+        pg.update();
+        assert_eq!(pg.frame_count(), 1);
     }
 }